@@ -2,16 +2,75 @@
 """AE PIPL Extractor - Extract and decompile Adobe After Effects PIPL resources."""
 
 import argparse
+import asyncio
+import binascii
+import glob
+import json
+import logging
+import mmap
+import struct
 import sys
 import os
+import re
+import threading
+import time
+import zipfile
+from collections import Counter
+from contextlib import contextmanager
+from dataclasses import dataclass, field, replace
 from pathlib import Path
-from typing import List, Optional
+from typing import Callable, Dict, Iterator, List, Optional, Tuple, TypeVar
 
-from resource_fork_parser import ResourceForkParser
+from resource_fork_parser import (
+    PIPL_RESOURCE_TYPE, ParseConfig, ResourceForkParser, ResourceTypeSummary, appledouble_resource_fork_range,
+    dump_resource_map_ascii, extract_appledouble_resource_fork, hex_dump, is_appledouble, summarize_resource_types,
+    total_data_bytes, total_resource_count
+)
 from rcp_parser import RcpParser
 from r_generator import RGenerator
-from pipl_types import PiplProperty
+from pipl_types import (
+    OutFlags2, PiplEntry, PiplProperty, PluginMetadata, Stage, TaggedVersionInfo, VersionInfo, VersionSource,
+    MAX_PIPL_PROPERTIES, PIPL_PROPERTY_TYPES, AE_SDK_CHANGELOG, AmbiguousUniversalBinaryError,
+    AE_REQUIRED_PROPERTY_TYPES, PiplError, extract_pf_version, match_name_problems, is_ae_effect_plugin,
+    is_valid_ae_plugin, normalize_property_type
+)
 from aex_resource_extractor import AexResourceExtractor
+from completions import COMPLETION_SHELLS, generate_completion
+from macho_parser import MachOParser, is_mach_o
+from pipl_fork_diff import PiplForkDiff
+from pipl_parser import find_property_range, parse_pipl_data_with_offsets, parse_pipl_from_base64
+from plugin_cache import build_cache_entry, load_cache, plugin_mtime_ns, write_cache
+from plugin_lockfile import write_lockfile, check_lockfile
+
+logger = logging.getLogger(__name__)
+
+T = TypeVar('T')
+
+# Exit codes, so scripts can tell failures apart
+EXIT_OK = 0
+EXIT_ERROR = 1  # Bad arguments, unreadable files or malformed data
+EXIT_VERSION_NOT_FOUND = 2  # The file has no effect version (or dump: no such property)
+EXIT_UNKNOWN_FORMAT = 3  # The file type could not be detected, or is not supported
+EXIT_REQUIREMENT_NOT_MET = 4  # --min-version, --check-compat or --check-pipl-version failed
+EXIT_TIMEOUT = 5  # Parsing took longer than --timeout
+
+class CliArgumentParser(argparse.ArgumentParser):
+    """ArgumentParser that exits with EXIT_ERROR, since argparse's default of 2 is EXIT_VERSION_NOT_FOUND."""
+
+    def error(self, message: str):
+        self.print_usage(sys.stderr)
+        self.exit(EXIT_ERROR, f"{self.prog}: error: {message}\n")
+
+# File extensions picked up when scanning a directory of plugins
+PLUGIN_EXTENSIONS = ('.rsrc', '.rcp', '.aex', '.dll', '.dylib', '.plugin')
+# Files read from ZIP archives, which are parsed in memory
+ZIP_MEMBER_EXTENSIONS = ('.rsrc', '.aex')
+
+# Raw disk images start with 1024 reserved bytes followed by the volume header
+HFS_VOLUME_HEADER_OFFSET = 1024
+HFS_SIGNATURES = (b'BD', b'H+', b'HX')
+# Reading a file out of a volume means walking its catalog B-tree, which this tool doesn't do
+HFS_UNSUPPORTED_MESSAGE = "HFS images are not supported; mount the image and pass the .plugin bundle or .rsrc file inside it."
 
 def detect_file_type(file_path: str) -> Optional[str]:
     """Detect the type of input file based on extension and content."""
@@ -27,10 +86,12 @@ def detect_file_type(file_path: str) -> Optional[str]:
         return 'rsrc'
     elif extension == '.rcp':
         return 'rcp'
-    elif extension == '.aex':
+    elif extension in ('.aex', '.dll'):
         return 'aex'
     elif extension == '.plugin':
         return 'plugin'
+    elif extension == '.dylib':
+        return 'macho'
 
     # Check if it's a directory (plugin bundle)
     if path.is_dir() and path.name.endswith('.plugin'):
@@ -39,7 +100,13 @@ def detect_file_type(file_path: str) -> Optional[str]:
     # Try to detect by content
     try:
         with open(file_path, 'rb') as f:
-            header = f.read(1024)
+            header = f.read(HFS_VOLUME_HEADER_OFFSET + 2)
+
+        # Check for an HFS/HFS+ disk image before the looser resource fork check
+        if is_hfs_image(header):
+            return 'hfs'
+
+        header = header[:1024]
 
         # Check for RCP text format
         if b'PiPL' in header and b'BEGIN' in header:
@@ -49,6 +116,14 @@ def detect_file_type(file_path: str) -> Optional[str]:
         if header[:2] == b'MZ':
             return 'aex'
 
+        # Check for an AppleDouble ('._' file) wrapping a resource fork
+        if is_appledouble(header):
+            return 'rsrc'
+
+        # Check for Mach-O binary (thin or universal)
+        if is_mach_o(header):
+            return 'macho'
+
         # Check for resource fork binary format
         if b'8BIM' in header or len(header) > 256:
             return 'rsrc'
@@ -58,6 +133,71 @@ def detect_file_type(file_path: str) -> Optional[str]:
 
     return None
 
+def is_hfs_image(data: bytes) -> bool:
+    """Check for an HFS, HFS+ or HFSX volume header signature."""
+    return data[HFS_VOLUME_HEADER_OFFSET:HFS_VOLUME_HEADER_OFFSET + 2] in HFS_SIGNATURES
+
+def score_file_formats(data: bytes) -> Dict[str, int]:
+    """Score how likely the data is to be each binary container format (0-100).
+
+    Unlike `detect_file_type`, which makes a single decision, this returns a
+    score for every candidate so callers can see the confidence distribution.
+    """
+    scores = {
+        'resource_fork': 0,
+        '8bim': 0,
+        'appledouble': 0,
+        'macbinary': 0,
+        'pe': 0
+    }
+
+    # Mac resource fork: 16-byte header describing data and map sections
+    if len(data) >= 16:
+        data_offset, map_offset, data_length, map_length = struct.unpack('>IIII', data[:16])
+        if data_offset >= 16 and data_length > 0 and data_offset + data_length <= len(data):
+            scores['resource_fork'] += 30
+        if map_length >= 30 and map_offset + map_length <= len(data):
+            scores['resource_fork'] += 30
+        if map_offset >= data_offset + data_length:
+            scores['resource_fork'] += 10
+        # The resource map normally starts with a copy of the header
+        if data[map_offset:map_offset + 16] in (data[:16], b'\x00' * 16):
+            scores['resource_fork'] += 30
+
+    # 8BIM property stream: signatures followed by known property keys
+    bim_offset = data.find(b'8BIM')
+    if bim_offset >= 0:
+        scores['8bim'] += 50
+        if data[bim_offset + 4:bim_offset + 8] in PIPL_PROPERTY_TYPES:
+            scores['8bim'] += 30
+        if data.find(b'8BIM', bim_offset + 4) >= 0:
+            scores['8bim'] += 20
+
+    # AppleDouble: magic number followed by version 2
+    if data[:4] == b'\x00\x05\x16\x07':
+        scores['appledouble'] += 70
+        if data[4:8] == b'\x00\x02\x00\x00':
+            scores['appledouble'] += 30
+
+    # MacBinary: 128-byte header with zero bytes at fixed positions and a CRC
+    if len(data) >= 128 and data[0] == 0 and data[74] == 0 and data[82] == 0:
+        if 1 <= data[1] <= 63:
+            scores['macbinary'] += 40
+        if data[102:106] == b'mBIN':
+            scores['macbinary'] += 60
+        elif binascii.crc_hqx(data[:124], 0) == struct.unpack('>H', data[124:126])[0]:
+            scores['macbinary'] += 60
+
+    # PE executable: DOS header pointing at a PE signature
+    if data[:2] == b'MZ':
+        scores['pe'] += 40
+        if len(data) >= 64:
+            pe_offset = struct.unpack('<I', data[60:64])[0]
+            if data[pe_offset:pe_offset + 4] == b'PE\x00\x00':
+                scores['pe'] += 60
+
+    return {name: min(score, 100) for name, score in scores.items()}
+
 def find_rsrc_in_plugin(plugin_path: str) -> Optional[str]:
     """Find .rsrc file inside a .plugin bundle."""
     plugin_dir = Path(plugin_path)
@@ -74,24 +214,136 @@ def find_rsrc_in_plugin(plugin_path: str) -> Optional[str]:
 
     return None
 
-def parse_file(file_path: str, file_type: str) -> List[PiplProperty]:
-    """Parse the input file and extract PIPL properties."""
-    properties = []
+def find_binary_in_plugin(plugin_path: str) -> Optional[str]:
+    """Find the Mach-O executable inside a .plugin bundle."""
+    macos_dir = Path(plugin_path) / "Contents" / "MacOS"
+    if macos_dir.exists():
+        for binary_file in macos_dir.iterdir():
+            if binary_file.is_file():
+                with open(binary_file, 'rb') as f:
+                    if is_mach_o(f.read(4)):
+                        return str(binary_file)
+
+    return None
 
+def parse_macho_file(file_path: str, arch: Optional[str] = None,
+                     max_properties: int = MAX_PIPL_PROPERTIES) -> List[PiplProperty]:
+    """Parse the __rsrc section of each Mach-O slice and return the agreed-upon properties."""
+    sections = MachOParser(file_path).extract_rsrc_sections()
+
+    if arch:
+        sections = [section for section in sections if section['arch'] == arch]
+        if not sections:
+            raise ValueError(f"No __rsrc section found for architecture '{arch}'")
+
+    slice_properties = []
+    for section in sections:
+        parser = ResourceForkParser.from_bytes(section['data'], file_path)
+        properties = parser.parse_pipl_properties(max_properties)
+        if properties:
+            slice_properties.append((section['arch'], properties))
+
+    if not slice_properties:
+        return []
+
+    # Every slice of a universal binary should describe the same effect version
+    versions = {}
+    for slice_arch, properties in slice_properties:
+        version_data = next((prop.data for prop in properties if prop.property_type == 'eVER'), None)
+        versions[slice_arch] = version_data.hex() if version_data else None
+
+    if len(set(versions.values())) > 1:
+        details = ", ".join(f"{slice_arch}: {version}" for slice_arch, version in versions.items())
+        raise AmbiguousUniversalBinaryError(
+            f"Universal binary slices disagree on effect version ({details}); use --arch to pick one"
+        )
+
+    return slice_properties[0][1]
+
+def parse_rsrc_file(file_path: str, config: Optional[ParseConfig] = None) -> List[PiplEntry]:
+    """Parse every PiPL resource in a .rsrc file.
+
+    Raises PiplError for data that can't be a resource fork, such as a file too
+    short for its header; other read errors are logged and yield no entries.
+    """
+    try:
+        parser = ResourceForkParser(file_path)
+
+        # '._' files copied to non-HFS filesystems wrap the resource fork in AppleDouble
+        if is_appledouble(parser.data):
+            fork_range = appledouble_resource_fork_range(parser.data)
+            if fork_range is None:
+                logger.warning(f"Warning: AppleDouble file {file_path} has no resource fork")
+                return []
+            fork_offset, fork_length = fork_range
+            logger.info(f"Found resource fork in AppleDouble file ({fork_length} bytes)")
+            parser = ResourceForkParser.from_bytes(bytes(parser.data[fork_offset:fork_offset + fork_length]),
+                                                   file_path, fork_offset)
+
+        entries = parser.parse_pipl_entries(config)
+
+        if not entries:
+            logger.warning(f"Warning: No PIPL properties found in {file_path}")
+            # Print debug info
+            info = parser.get_file_info()
+            logger.info(f"File info: {info}")
+
+        return entries
+
+    except PiplError:
+        raise
+    except (OSError, ValueError, struct.error) as e:
+        logger.error(f"Error parsing .rsrc file: {e}")
+        return []
+
+async def parse_rsrc_file_async(file_path: str, config: Optional[ParseConfig] = None) -> List[PiplEntry]:
+    """Run `parse_rsrc_file` in the default executor so async callers don't block their event loop."""
+    loop = asyncio.get_running_loop()
+    return await loop.run_in_executor(None, parse_rsrc_file, file_path, config)
+
+def parse_file_entries(file_path: str, file_type: str, arch: Optional[str] = None,
+                       config: Optional[ParseConfig] = None) -> List[PiplEntry]:
+    """Parse the input file and return every PiPL it contains.
+
+    Resource forks can hold several PiPL resources (for example a Photoshop and an
+    After Effects variant); every other format yields at most one entry.
+    """
     if file_type == 'rsrc':
-        try:
-            parser = ResourceForkParser(file_path)
-            properties = parser.parse_pipl_properties()
+        return parse_rsrc_file(file_path, config)
 
-            if not properties:
-                print(f"Warning: No PIPL properties found in {file_path}")
-                # Print debug info
-                info = parser.get_file_info()
-                print(f"File info: {info}")
+    if file_type == 'plugin':
+        rsrc_path = find_rsrc_in_plugin(file_path)
+        if rsrc_path:
+            logger.info(f"Found .rsrc file: {rsrc_path}")
+            return parse_rsrc_file(rsrc_path, config)
 
-        except Exception as e:
-            print(f"Error parsing .rsrc file: {e}")
-            return []
+    properties = parse_file(file_path, file_type, arch, config=config)
+    return [PiplEntry.from_properties(properties, file_type)] if properties else []
+
+def select_pipl_entry(entries: List[PiplEntry], file_path: str,
+                      resource_id: Optional[int] = None) -> Optional[PiplEntry]:
+    """Pick the PiPL with the given resource ID, or the first one when no ID is given."""
+    if resource_id is not None:
+        entry = next((entry for entry in entries if entry.resource_id == resource_id), None)
+        if entry is None:
+            logger.error(f"No PiPL resource with ID {resource_id} found in {file_path}.")
+        return entry
+
+    if len(entries) > 1:
+        ids = ', '.join(str(entry.resource_id) for entry in entries)
+        logger.warning(f"Warning: {file_path} contains {len(entries)} PiPL resources ({ids}); "
+                       f"using the first. Pass --resource-id to choose another.")
+    return entries[0] if entries else None
+
+def parse_file(file_path: str, file_type: str, arch: Optional[str] = None,
+               resource_id: Optional[int] = None, config: Optional[ParseConfig] = None) -> List[PiplProperty]:
+    """Parse the input file and extract the properties of its first PiPL, or of the PiPL with resource_id."""
+    config = config or ParseConfig()
+    properties = []
+
+    if file_type == 'rsrc':
+        entry = select_pipl_entry(parse_rsrc_file(file_path, config), file_path, resource_id)
+        properties = entry.properties if entry else []
 
     elif file_type == 'rcp':
         try:
@@ -99,12 +351,12 @@ def parse_file(file_path: str, file_type: str) -> List[PiplProperty]:
             properties = parser.parse_pipl_properties()
 
             if not properties:
-                print(f"Warning: No PIPL properties found in {file_path}")
+                logger.warning(f"Warning: No PIPL properties found in {file_path}")
                 info = parser.get_file_info()
-                print(f"File info: {info}")
+                logger.info(f"File info: {info}")
 
         except Exception as e:
-            print(f"Error parsing .rcp file: {e}")
+            logger.error(f"Error parsing .rcp file: {e}")
             return []
 
     elif file_type == 'aex':
@@ -113,35 +365,929 @@ def parse_file(file_path: str, file_type: str) -> List[PiplProperty]:
             properties = extractor.extract_pipl_properties()
 
             if not properties:
-                print(f"Warning: No PIPL properties found in {file_path}")
+                logger.warning(f"Warning: No PIPL properties found in {file_path}")
 
         except Exception as e:
-            print(f"Error parsing .aex file: {e}")
+            logger.error(f"Error parsing .aex file: {e}")
+            return []
+
+    elif file_type == 'macho':
+        try:
+            properties = parse_macho_file(file_path, arch, config.max_properties)
+
+            if not properties:
+                logger.warning(f"Warning: No PIPL properties found in {file_path}")
+
+        # AmbiguousUniversalBinaryError is left to the caller, which tells the user to pick a slice
+        except (OSError, ValueError, struct.error) as e:
+            logger.error(f"Error parsing Mach-O file: {e}")
             return []
 
+    elif file_type == 'hfs':
+        logger.error(f"Error: {file_path}: {HFS_UNSUPPORTED_MESSAGE}")
+        return []
+
     elif file_type == 'plugin':
         try:
             # Find .rsrc file inside plugin bundle
             rsrc_path = find_rsrc_in_plugin(file_path)
+            binary_path = find_binary_in_plugin(file_path)
             if rsrc_path:
-                print(f"Found .rsrc file: {rsrc_path}")
-                parser = ResourceForkParser(rsrc_path)
-                properties = parser.parse_pipl_properties()
-
-                if not properties:
-                    print(f"Warning: No PIPL properties found in {rsrc_path}")
+                logger.info(f"Found .rsrc file: {rsrc_path}")
+                entry = select_pipl_entry(parse_rsrc_file(rsrc_path, config), rsrc_path, resource_id)
+                properties = entry.properties if entry else []
+            elif binary_path:
+                # No sidecar .rsrc, fall back to a __rsrc section in the executable
+                logger.info(f"Found Mach-O binary: {binary_path}")
+                properties = parse_file(binary_path, 'macho', arch, config=config)
             else:
-                print(f"Error: No .rsrc file found in plugin bundle {file_path}")
+                logger.error(f"Error: No .rsrc file found in plugin bundle {file_path}")
                 return []
 
-        except Exception as e:
-            print(f"Error parsing .plugin bundle: {e}")
+        except (OSError, ValueError, struct.error) as e:
+            logger.error(f"Error parsing .plugin bundle: {e}")
             return []
 
     return properties
 
-def main():
-    parser = argparse.ArgumentParser(
+def expand_glob_patterns(paths: List[str]) -> List[str]:
+    """Expand wildcard arguments, which cmd.exe and PowerShell pass through unexpanded."""
+    expanded = []
+
+    for path in paths:
+        if any(char in path for char in '*?['):
+            matches = sorted(glob.glob(path))
+            if not matches:
+                print(f"No files match '{path}'", file=sys.stderr)
+            expanded.extend(matches)
+        else:
+            expanded.append(path)
+
+    return expanded
+
+def directory_plugin_paths(directory: str, recursive: bool = False) -> List[str]:
+    """List the plug-in files and bundles in a directory.
+
+    With recursive, subdirectories are searched too and ZIP archives are included.
+    """
+    paths = []
+
+    for entry in sorted(Path(directory).iterdir()):
+        suffix = entry.suffix.lower()
+        if suffix in PLUGIN_EXTENSIONS or (recursive and suffix == '.zip'):
+            paths.append(str(entry))
+        elif recursive and entry.is_dir():
+            paths.extend(directory_plugin_paths(str(entry), recursive))
+
+    return paths
+
+def expand_input_paths(paths: List[str], recursive: bool = False) -> List[str]:
+    """Expand plain directories into the plug-in files and bundles they contain."""
+    expanded = []
+
+    # Unix shells have already expanded globs, and a literal '*' there is a real file name
+    if os.name == 'nt':
+        paths = expand_glob_patterns(paths)
+
+    for path in paths:
+        if os.path.isdir(path) and not path.rstrip('/\\').endswith('.plugin'):
+            expanded.extend(directory_plugin_paths(path, recursive))
+        else:
+            expanded.append(path)
+
+    return expanded
+
+def format_toml_plugin(input_file: str, version_info: VersionInfo, array: bool) -> str:
+    """Format an effect version as a TOML [plugin] table, or a [[plugin]] entry in batch mode."""
+    lines = ['[[plugin]]' if array else '[plugin]']
+    # JSON string escapes are a subset of TOML basic string escapes
+    lines.append(f"path = {json.dumps(input_file)}")
+    for key, value in version_info.to_dict().items():
+        lines.append(f"{key} = {json.dumps(value)}")
+    lines.append(f"raw_encoded = {version_info.encode_to_hex_string()}")
+
+    return '\n'.join(lines) + '\n'
+
+def detect_multiple_formats(data: bytes) -> List[str]:
+    """Return every file type whose structural checks the data passes.
+
+    `detect_file_type` stops at the first match; this keeps going so ambiguous
+    files (such as a resource fork that also contains 8BIM byte sequences
+    matching several checks) can be reported or retried under another format.
+    Types are returned in the order `detect_file_type` tries them.
+    """
+    formats = []
+
+    if is_hfs_image(data):
+        formats.append('hfs')
+
+    if b'PiPL' in data[:1024] and b'BEGIN' in data[:1024]:
+        formats.append('rcp')
+
+    # PE executable: DOS header pointing at a 'PE\0\0' signature
+    if data[:2] == b'MZ' and len(data) >= 64:
+        pe_offset = struct.unpack('<I', data[60:64])[0]
+        if data[pe_offset:pe_offset + 4] == b'PE\x00\x00':
+            formats.append('aex')
+
+    if is_mach_o(data):
+        formats.append('macho')
+
+    scores = score_file_formats(data)
+    if scores['resource_fork'] >= 70 or is_appledouble(data) or scores['8bim'] >= 80:
+        formats.append('rsrc')
+
+    return formats
+
+@dataclass
+class ProcessResult:
+    """Outcome of parsing one input file: its PiPL entries, or why none could be read."""
+    path: str
+    entries: List[PiplEntry] = field(default_factory=list)
+    error: Optional[str] = None
+    exit_code: int = EXIT_OK
+
+    @property
+    def ok(self) -> bool:
+        return self.error is None
+
+@contextmanager
+def map_input_file(path: str) -> Iterator[bytes]:
+    """Memory-map a file for format detection, so the checks only read the pages they look at."""
+    with open(path, 'rb') as f:
+        try:
+            mapping = mmap.mmap(f.fileno(), 0, access=mmap.ACCESS_READ)
+        except (ValueError, OSError):
+            # Empty files and some filesystems can't be mapped
+            mapping = None
+        if mapping is None:
+            yield f.read()
+            return
+
+    with mapping:
+        yield mapping
+
+def parse_input_file(input_file: str, args: argparse.Namespace) -> ProcessResult:
+    """Detect and parse a single input file without printing its entries."""
+    # Check input file
+    if not os.path.exists(input_file):
+        return ProcessResult(input_file, error=f"Input file '{input_file}' not found.", exit_code=EXIT_ERROR)
+
+    # Detect file type, sharing one mapping of the file between the content checks
+    file_type = args.force_type or detect_file_type(input_file)
+    candidates = []
+    scores = None
+    if os.path.isfile(input_file) and (not args.force_type or args.report_format_detection_confidence):
+        with map_input_file(input_file) as data:
+            if not args.force_type:
+                candidates = detect_multiple_formats(data)
+            if args.report_format_detection_confidence:
+                scores = score_file_formats(data)
+
+    if args.strict and len(candidates) > 1:
+        return ProcessResult(input_file, error=f"'{input_file}' is valid as several formats "
+                                               f"({', '.join(candidates)}). Use --force-type to choose one.",
+                             exit_code=EXIT_UNKNOWN_FORMAT)
+
+    if not file_type:
+        return ProcessResult(input_file, error=f"Could not detect file type for '{input_file}'. "
+                                               "Use --force-type to specify the file type manually.",
+                             exit_code=EXIT_UNKNOWN_FORMAT)
+
+    if file_type == 'hfs':
+        return ProcessResult(input_file, error=f"{input_file}: {HFS_UNSUPPORTED_MESSAGE}", exit_code=EXIT_UNKNOWN_FORMAT)
+
+    logger.info(f"Detected file type: {file_type}")
+
+    if scores is not None:
+        print("Format detection confidence:")
+        for format_name, score in sorted(scores.items(), key=lambda item: -item[1]):
+            print(f"  {format_name:<14} {score:>3}")
+
+    # Parse the file
+    logger.info(f"Parsing {input_file}...")
+    config = ParseConfig(max_properties=args.max_properties)
+    entries = []
+    parse_error = None
+
+    # Fall back to the other plausible formats before giving up
+    for candidate in [file_type] + [candidate for candidate in candidates if candidate != file_type]:
+        if candidate != file_type:
+            logger.info(f"Retrying {input_file} as {candidate}...")
+        try:
+            entries = parse_file_entries(input_file, candidate, args.arch, config)
+        except AmbiguousUniversalBinaryError as e:
+            # Reading the file as another format would silently pick one of the slices
+            return ProcessResult(input_file, error=str(e), exit_code=EXIT_ERROR)
+        except PiplError as e:
+            parse_error = parse_error or e
+            continue
+        if entries:
+            break
+
+    if not entries:
+        if parse_error is not None:
+            return ProcessResult(input_file, error=f"Could not parse {input_file}: {parse_error}", exit_code=EXIT_ERROR)
+        return ProcessResult(input_file, error=f"No PIPL properties found in {input_file}.", exit_code=EXIT_ERROR)
+
+    return ProcessResult(input_file, entries)
+
+def is_zip_archive(path: str) -> bool:
+    """Check whether an input path is a ZIP archive to be read with parse_zip_archive()."""
+    return path.lower().endswith('.zip') and os.path.isfile(path)
+
+def parse_zip_member(member_path: str, data: bytes, config: ParseConfig) -> List[PiplEntry]:
+    """Parse a .rsrc or .aex file read from a ZIP archive."""
+    if member_path.lower().endswith('.aex'):
+        properties = AexResourceExtractor.from_bytes(data, member_path).extract_pipl_properties()
+        return [PiplEntry.from_properties(properties, 'aex')] if properties else []
+
+    return ResourceForkParser.from_bytes(data, member_path, 0).parse_pipl_entries(config)
+
+def parse_zip_archive(zip_path: str, args: argparse.Namespace) -> List[ProcessResult]:
+    """Parse every .rsrc and .aex file in a ZIP archive without extracting it to disk.
+
+    Each result is identified by the archive path and the path inside it,
+    e.g. 'Plugins.zip:Glow.plugin/Contents/Resources/Glow.rsrc'.
+    """
+    config = ParseConfig(max_properties=args.max_properties)
+    results = []
+
+    try:
+        with zipfile.ZipFile(zip_path) as archive:
+            for info in archive.infolist():
+                # Skip the AppleDouble files macOS adds under __MACOSX/
+                if (info.is_dir() or info.filename.startswith('__MACOSX/')
+                        or not info.filename.lower().endswith(ZIP_MEMBER_EXTENSIONS)):
+                    continue
+                member_path = f"{zip_path}:{info.filename}"
+                logger.info(f"Parsing {member_path}...")
+                try:
+                    entries = parse_zip_member(member_path, archive.read(info), config)
+                except (ValueError, PiplError) as e:
+                    results.append(ProcessResult(member_path, error=f"Could not parse {member_path}: {e}",
+                                                 exit_code=EXIT_ERROR))
+                    continue
+                if entries:
+                    results.append(ProcessResult(member_path, entries))
+                else:
+                    results.append(ProcessResult(member_path, error=f"No PIPL properties found in {member_path}.",
+                                                 exit_code=EXIT_ERROR))
+    except (zipfile.BadZipFile, OSError) as e:
+        return [ProcessResult(zip_path, error=f"Could not read ZIP archive '{zip_path}': {e}", exit_code=EXIT_ERROR)]
+
+    if not results:
+        logger.warning(f"Warning: No .rsrc or .aex files found in {zip_path}")
+    return results
+
+def run_with_timeout(timeout: Optional[float], func: Callable[..., T], *func_args) -> T:
+    """Call func, exiting with EXIT_TIMEOUT if it hasn't returned after timeout seconds.
+
+    The call runs in a daemon thread, since Python can't interrupt a thread that
+    is busy parsing; exiting the process is what stops it.
+    """
+    if timeout is None:
+        return func(*func_args)
+
+    outcome = {}
+
+    def run():
+        try:
+            outcome['result'] = func(*func_args)
+        except BaseException as e:
+            outcome['error'] = e
+
+    thread = threading.Thread(target=run, daemon=True)
+    thread.start()
+    thread.join(timeout)
+    if thread.is_alive():
+        print(f"Parse timed out after {timeout:g} seconds", file=sys.stderr)
+        sys.stdout.flush()
+        os._exit(EXIT_TIMEOUT)
+    if 'error' in outcome:
+        raise outcome['error']
+    return outcome['result']
+
+def process_file(input_file: str, args: argparse.Namespace, batch: bool = False) -> ProcessResult:
+    """Detect, parse and print a single input file."""
+    result = run_with_timeout(args.timeout, parse_input_file, input_file, args)
+    if not result.ok:
+        logger.error(f"Error: {result.error}")
+        return result
+
+    return print_result(result, args, batch)
+
+def print_result(result: ProcessResult, args: argparse.Namespace, batch: bool = False) -> ProcessResult:
+    """Print a parsed file, returning a result with the entries that were printed."""
+    entries = output_entries(result.path, result.entries, args, batch)
+    if entries is None:
+        # output_entries has already logged why: a missing eVER, or a --resource-id the file doesn't have
+        missing_version = any(entry.version_info is None for entry in result.entries)
+        return ProcessResult(result.path, error=f"Could not print the PiPL of {result.path}.",
+                             exit_code=EXIT_VERSION_NOT_FOUND if missing_version else EXIT_ERROR)
+
+    return ProcessResult(result.path, entries)
+
+def list_properties(entry: PiplEntry) -> List[Dict[str, object]]:
+    """Describe each property's signature, key and length without decoding its value."""
+    # Windows resources store the signature byte-reversed
+    signature = 'MIB8' if entry.format == 'aex' else '8BIM'
+    rows = []
+
+    for prop in entry.properties:
+        key = prop.kind_code()
+        # Keys that aren't known PiPL properties may not be printable, so show them as hex only
+        rows.append({
+            'signature': signature,
+            'key': key.decode('mac_roman') if key in PIPL_PROPERTY_TYPES else key.hex(),
+            'key_hex': f"0x{key.hex()}",
+            'name': prop.key_as_str(),
+            'length': len(prop.data)
+        })
+
+    return rows
+
+# Placeholders --output-template can use; anything else in braces is printed as-is
+OUTPUT_TEMPLATE_FIELDS = ('version', 'subversion', 'bugversion', 'stage', 'build', 'raw', 'name', 'path')
+OUTPUT_TEMPLATE_PLACEHOLDER = re.compile(r'\{(\w*)\}')
+
+def unknown_template_placeholders(template: str) -> List[str]:
+    """Return the placeholders in an --output-template that render_output_template won't substitute."""
+    return sorted({name for name in OUTPUT_TEMPLATE_PLACEHOLDER.findall(template)
+                   if name not in OUTPUT_TEMPLATE_FIELDS})
+
+def render_output_template(template: str, input_file: str, entry: PiplEntry) -> str:
+    """Substitute an entry's version fields, encoded eVER, name and path into an --output-template."""
+    version_info = entry.version_info
+    values = {
+        'version': str(version_info.version),
+        'subversion': str(version_info.subversion),
+        'bugversion': str(version_info.bugversion),
+        'stage': str(version_info.stage),
+        'build': str(version_info.build),
+        'raw': version_info.encode_to_hex_string(),
+        'name': PluginMetadata.from_properties(entry.properties).display_name(),
+        'path': input_file
+    }
+    return OUTPUT_TEMPLATE_PLACEHOLDER.sub(lambda match: values.get(match.group(1), match.group(0)), template)
+
+def output_entries(input_file: str, entries: List[PiplEntry], args: argparse.Namespace,
+                   batch: bool = False) -> Optional[List[PiplEntry]]:
+    """Print parsed PiPL entries in the requested format. Returns None on failure."""
+    if args.resource_id is not None:
+        entries = [entry for entry in entries if entry.resource_id == args.resource_id]
+        if not entries:
+            logger.error(f"No PiPL resource with ID {args.resource_id} found in {input_file}.")
+            return None
+
+    if args.ae_effects_only:
+        entries = [entry for entry in entries if is_ae_effect_plugin(entry.properties)]
+        if not entries:
+            logger.info(f"Skipping {input_file}: not an After Effects effect.")
+            return entries
+
+    if args.signature:
+        entries = [replace(entry, properties=[prop for prop in entry.properties if prop.signature in args.signature])
+                   for entry in entries]
+
+    # Validate the match name here, once per PiPL, rather than each time its metadata is rebuilt
+    for entry in entries:
+        match_name_data = next((prop.data for prop in entry.properties
+                                if normalize_property_type(prop.property_type) == 'eMNA'), None)
+        if match_name_data is not None:
+            for problem in match_name_problems(match_name_data):
+                logger.warning(f"Warning: {problem}")
+
+    if args.list_properties:
+        listings = [list_properties(entry) for entry in entries]
+        if args.format == 'json':
+            print(json.dumps(listings[0] if len(listings) == 1 else listings, indent=2))
+            return entries
+        for entry, listing in zip(entries, listings):
+            if len(entries) > 1:
+                print(f"PiPL resource ID {entry.resource_id}:")
+            print(f"{'Signature':<10} {'Key':<6} {'Hex':<10} {'Name':<28} {'Length':>6}")
+            for row in listing:
+                # Fall back to the quoted raw key for keys without a human-readable name
+                name = row['name'] or f"'{row['key']}'"
+                print(f"{row['signature']:<10} {row['key']:<6} {row['key_hex']:<10} {name:<28} {row['length']:>6}")
+        return entries
+
+    if args.output_template is not None:
+        for entry in entries:
+            if not entry.version_info:
+                logger.error(f"No effect version (eVER) found in {input_file}.")
+                return None
+            print(render_output_template(args.output_template, input_file, entry))
+        return entries
+
+    if args.format == 'json':
+        summaries = [entry.summary().to_dict() for entry in entries]
+        print(json.dumps(summaries[0] if len(summaries) == 1 else summaries, indent=2))
+        return entries
+
+    if args.format == 'toml':
+        for entry in entries:
+            if not entry.version_info:
+                logger.error(f"No effect version (eVER) found in {input_file}.")
+                return None
+            print(format_toml_plugin(input_file, entry.version_info, array=batch or len(entries) > 1))
+        return entries
+
+    for entry in entries:
+        if not print_entry(input_file, entry, args, labelled=len(entries) > 1):
+            return None
+
+    return entries
+
+ANSI_COLORS = {
+    'green': '\033[32m',
+    'yellow': '\033[33m',
+    'red': '\033[31m'
+}
+ANSI_RESET = '\033[0m'
+
+# PF_OutFlag2 bits used to judge plugin health
+OUT_FLAG2_THREADSAFE = OutFlags2.AE13_5_THREADSAFE | OutFlags2.SUPPORTS_THREADED_RENDERING
+OUT_FLAG2_GPU = OutFlags2.SUPPORTS_GPU_RENDER_F32
+
+def entry_severity(entry: PiplEntry) -> Optional[str]:
+    """Classify an entry as green (release, GPU), yellow (release, not thread-safe) or red (pre-release)."""
+    if not entry.version_info:
+        return None
+    if entry.version_info.stage != Stage.RELEASE:
+        return 'red'
+
+    flags2 = PluginMetadata.from_properties(entry.properties).outflags2 or OutFlags2(0)
+
+    if not flags2 & OUT_FLAG2_THREADSAFE:
+        return 'yellow'
+    if flags2 & OUT_FLAG2_GPU:
+        return 'green'
+    return None
+
+def color_enabled(args: argparse.Namespace) -> bool:
+    """Use ANSI colors only on a terminal, and never with --no-color or a non-empty NO_COLOR (no-color.org)."""
+    return not args.no_color and not os.environ.get('NO_COLOR') and sys.stdout.isatty()
+
+def colorize_severity(line: str, entry: PiplEntry) -> str:
+    """Color a line by the entry's health. Callers check color_enabled first."""
+    severity = entry_severity(entry)
+    if not severity:
+        return line
+    return f"{ANSI_COLORS[severity]}{line}{ANSI_RESET}"
+
+def format_version(version_info: VersionInfo, args: argparse.Namespace) -> str:
+    """Format an effect version as selected by --version-format."""
+    return version_info.to_semver() if args.version_format == 'semver' else str(version_info)
+
+def print_entry(input_file: str, entry: PiplEntry, args: argparse.Namespace, labelled: bool) -> bool:
+    """Print one PiPL entry. Returns False if a required value is missing."""
+    properties = entry.properties
+    label = f"PiPL resource ID {entry.resource_id}" if entry.resource_id is not None else "PiPL"
+
+    if labelled:
+        logger.info(f"\n{label}:")
+
+    if entry.version_source == VersionSource.VERS:
+        logger.warning("Warning: No PiPL resource found, using the version from the 'vers' resource")
+        if args.quiet:
+            print(format_version(entry.version_info, args))
+        else:
+            print(f"Version ('vers' resource): {format_version(entry.version_info, args)}")
+        return True
+
+    # Only effects must declare these; AEGPs and Photoshop filters have their own requirements
+    if is_ae_effect_plugin(properties) and not is_valid_ae_plugin(properties):
+        present = {normalize_property_type(prop.property_type) for prop in properties}
+        missing = [prop_type for prop_type in AE_REQUIRED_PROPERTY_TYPES if prop_type not in present]
+        logger.warning(f"Warning: Missing properties required by After Effects: {', '.join(missing)}")
+
+    if args.quiet:
+        if not entry.version_info:
+            logger.error(f"No effect version (eVER) found in {input_file}.")
+            return False
+        version = format_version(entry.version_info, args)
+        line = f"{entry.resource_id}: {version}" if labelled else version
+        print(colorize_severity(line, entry) if args.color_severity and color_enabled(args) else line)
+        return True
+
+    metadata = PluginMetadata.from_properties(properties)
+    line = f"Plugin: {metadata.display_name()}"
+    print(colorize_severity(line, entry) if args.color_severity and color_enabled(args) else line)
+    if metadata.category is not None:
+        print(f"Category: {metadata.category}")
+    if metadata.match_name is not None:
+        print(f"Match name: {metadata.match_name}")
+    if args.version_format == 'semver' and entry.version_info is not None:
+        print(f"Version: {entry.version_info.to_semver()}")
+    if args.verbose and metadata.supported_modes is not None:
+        print(f"Supports: {', '.join(metadata.supported_modes.mode_names()) or 'none'}")
+    if args.verbose and metadata.outflags is not None:
+        print(f"Shows Dialog: {'yes' if metadata.shows_dialog() else 'no'}")
+    if args.verbose and metadata.outflags2 is not None:
+        print(f"OutFlags2: {', '.join(metadata.outflags2.flag_names()) or 'none'}")
+    version_offset = next((prop.data_offset for prop in properties
+                           if normalize_property_type(prop.property_type) == 'eVER'), None)
+    if args.verbose and version_offset is not None:
+        print(f"  Version offset: 0x{version_offset:X}")
+    if args.verbose:
+        print_pipl_hex_dump(input_file, entry, args)
+
+    logger.info(f"Found {len(properties)} PIPL properties: ")
+
+    # Generate .r file
+    generator = RGenerator(properties)
+    generator.print_info()
+
+    return True
+
+def print_pipl_hex_dump(input_file: str, entry: PiplEntry, args: argparse.Namespace) -> None:
+    """Print each property chunk of a .rsrc PiPL resource as a hex dump numbered by its offset in the resource."""
+    rsrc_path = find_rsrc_in_plugin(input_file) if os.path.isdir(input_file) else input_file
+    if entry.format != 'resource_fork' or entry.resource_id is None or not rsrc_path or not os.path.isfile(rsrc_path):
+        return
+
+    try:
+        pipl_data = ResourceForkParser(rsrc_path).resource_data(PIPL_RESOURCE_TYPE, entry.resource_id)
+        if pipl_data is None:
+            return
+        pipl_data = bytes(pipl_data)
+        properties = parse_pipl_data_with_offsets(pipl_data, ParseConfig(max_properties=args.max_properties))
+    except PiplError as e:
+        logger.warning(f"Warning: Could not dump PiPL resource {entry.resource_id}: {e}")
+        return
+
+    for offset, prop in properties:
+        # The chunk runs from the signature through the end of the property's data
+        print(f"  {prop.kind_code().decode('mac_roman')} at 0x{offset:X}:")
+        print(hex_dump(pipl_data[offset:offset + 16 + len(prop.data)], offset))
+
+def write_cmake_version_file(path: str, version_info: VersionInfo, source: str) -> None:
+    """Write a CMake file that sets PLUGIN_VERSION* variables for `include()`."""
+    lines = [
+        f"# Auto-generated by ae_pipl_extractor.py from {os.path.basename(source)}. Do not edit.",
+        f'set(PLUGIN_VERSION "{version_info.version}.{version_info.subversion}.{version_info.bugversion}")',
+        f"set(PLUGIN_VERSION_MAJOR {version_info.version})",
+        f"set(PLUGIN_VERSION_MINOR {version_info.subversion})",
+        f"set(PLUGIN_VERSION_PATCH {version_info.bugversion})",
+        f'set(PLUGIN_VERSION_STAGE "{version_info.stage.to_json_value()}")',
+        f"set(PLUGIN_VERSION_BUILD {version_info.build})"
+    ]
+
+    with open(path, 'w', encoding='utf-8') as f:
+        f.write('\n'.join(lines) + '\n')
+
+def parse_property_key(text: str, what: str = 'Property key') -> bytes:
+    """Parse a property key or signature given as a four-character OSType or a 0x-prefixed hex value."""
+    if text.lower().startswith('0x'):
+        value = int(text, 16)
+        if value > 0xFFFFFFFF:
+            raise ValueError(f"{what} {text} doesn't fit in 32 bits")
+        return struct.pack('>I', value)
+
+    key = text.encode('mac_roman')
+    if len(key) != 4:
+        raise ValueError(f"{what} must be 4 characters, got {text!r}")
+    return key
+
+def read_resource_fork(file_path: str) -> Optional[bytes]:
+    """Read a .rsrc file, unwrapping the resource fork of an AppleDouble ('._') file.
+
+    Returns None for an AppleDouble file that has no resource fork.
+    """
+    with open(file_path, 'rb') as f:
+        data = f.read()
+    if is_appledouble(data):
+        return extract_appledouble_resource_fork(data)
+    return data
+
+def pipl_resource_data_offset(file_path: str, file_type: str, entry: PiplEntry, key: bytes) -> Optional[int]:
+    """Return the offset of a property's data within its PiPL resource in a .rsrc file."""
+    if file_type != 'rsrc' or entry.resource_id is None:
+        return None
+
+    try:
+        fork_data = read_resource_fork(file_path)
+        if fork_data is None:
+            return None
+        pipl_data = ResourceForkParser.from_bytes(fork_data, file_path).resource_data(PIPL_RESOURCE_TYPE,
+                                                                                     entry.resource_id)
+        if pipl_data is None:
+            return None
+        property_range = find_property_range(bytes(pipl_data), key)
+        if property_range is not None:
+            # Skip the signature, key, reserved and length fields
+            return property_range[0] + 16
+    except PiplError:
+        pass
+
+    return None
+
+def dump_command(argv: List[str]) -> None:
+    """Print an annotated hex dump of one property's raw data.
+
+    Numeric properties read from Windows resources are shown after conversion
+    to big-endian, the same bytes the decoders see.
+    """
+    parser = CliArgumentParser(
+        prog=f"{os.path.basename(sys.argv[0])} dump",
+        description="Print the raw bytes of a PiPL property as a hex dump"
+    )
+    parser.add_argument('input_file', help='Input file (.rsrc, .rcp, .aex, .dll, .dylib, or .plugin bundle)')
+    parser.add_argument('--key', required=True, help="Property key as an OSType (e.g. eVER) or hex (e.g. 0x65564552)")
+    parser.add_argument('--force-type', choices=['rsrc', 'rcp', 'aex', 'plugin', 'macho'],
+                        help='Force file type detection')
+    parser.add_argument('--arch', choices=['arm64', 'x86_64'],
+                        help='Architecture slice to read from a universal Mach-O binary')
+    args = parser.parse_args(argv)
+
+    logging.basicConfig(level=logging.ERROR, format='%(message)s', stream=sys.stderr)
+
+    try:
+        key = parse_property_key(args.key)
+    except ValueError as e:
+        parser.error(str(e))
+
+    if not os.path.exists(args.input_file):
+        logger.error(f"Error: Input file '{args.input_file}' not found.")
+        sys.exit(EXIT_ERROR)
+
+    file_type = args.force_type or detect_file_type(args.input_file)
+    if not file_type:
+        logger.error(f"Error: Could not detect file type for '{args.input_file}'.")
+        sys.exit(EXIT_UNKNOWN_FORMAT)
+
+    key_name = key.decode('mac_roman')
+    try:
+        entries = parse_file_entries(args.input_file, file_type, args.arch)
+    except PiplError as e:
+        logger.error(f"Error: Could not parse {args.input_file}: {e}")
+        sys.exit(EXIT_ERROR)
+
+    for entry in entries:
+        for prop in entry.properties:
+            if prop.kind_code() == key:
+                label = f" in PiPL resource ID {entry.resource_id}" if entry.resource_id is not None else ""
+                data_offset = pipl_resource_data_offset(args.input_file, file_type, entry, key)
+                print(f"Property '{key_name}'{label}, {len(prop.data)} bytes:")
+                # Number the dump by position in the PiPL resource when the file has one
+                print(hex_dump(bytes(prop.data), data_offset or 0))
+                return
+
+    logger.error(f"Property '{key_name}' not found in {args.input_file}.")
+    sys.exit(EXIT_VERSION_NOT_FOUND)
+
+def patch_command(argv: List[str]) -> None:
+    """Write a new effect version over the encoded eVER value of a .rsrc file.
+
+    Only the 4 bytes of the encoded version change, so the resource map stays valid.
+    The result is written to a copy unless --in-place is given.
+    """
+    parser = CliArgumentParser(
+        prog=f"{os.path.basename(sys.argv[0])} patch",
+        description="Write a new effect version into the eVER property of a .rsrc file"
+    )
+    parser.add_argument('input_file', help='Input .rsrc file')
+    parser.add_argument('output_file', nargs='?', help='Where to write the patched copy')
+    version_group = parser.add_mutually_exclusive_group(required=True)
+    version_group.add_argument('--version', dest='new_version', metavar='VERSION',
+                               help='New effect version, e.g. "2.0.0 Release (Build 1)"')
+    version_group.add_argument('--increment-build', action='store_true',
+                               help='Bump the build number of the current effect version by one')
+    parser.add_argument('--in-place', action='store_true', help='Modify the input file instead of writing a copy')
+    parser.add_argument('--resource-id', type=int, metavar='N',
+                        help='PiPL resource to patch when the file contains several')
+    args = parser.parse_args(argv)
+
+    logging.basicConfig(level=logging.ERROR, format='%(message)s', stream=sys.stderr)
+
+    if args.in_place == bool(args.output_file):
+        parser.error("give either an output file or --in-place")
+
+    if not os.path.isfile(args.input_file):
+        logger.error(f"Error: Input file '{args.input_file}' not found.")
+        sys.exit(EXIT_ERROR)
+
+    try:
+        entry = select_pipl_entry(parse_rsrc_file(args.input_file), args.input_file, args.resource_id)
+    except PiplError as e:
+        logger.error(f"Error: Could not parse {args.input_file}: {e}")
+        sys.exit(EXIT_ERROR)
+    version_prop = next((prop for prop in entry.properties if prop.property_type == 'eVER'), None) if entry else None
+    if version_prop is None or version_prop.data_offset is None:
+        logger.error(f"No effect version (eVER) found in {args.input_file}.")
+        sys.exit(EXIT_VERSION_NOT_FOUND)
+
+    old_version = entry.version_info
+    try:
+        new_version = old_version.increment_build() if args.increment_build else VersionInfo.from_str(args.new_version)
+        encoded = new_version.to_encoded()
+    except ValueError as e:
+        logger.error(f"Error: {e}")
+        sys.exit(EXIT_ERROR)
+
+    with open(args.input_file, 'rb') as f:
+        data = bytearray(f.read())
+    data[version_prop.data_offset:version_prop.data_offset + 4] = struct.pack('>I', encoded)
+
+    output_file = args.input_file if args.in_place else args.output_file
+    with open(output_file, 'wb') as f:
+        f.write(data)
+
+    print(f"{output_file}: {old_version} -> {new_version} (eVER {new_version.encode_to_hex_string()} "
+          f"at offset {version_prop.data_offset:#x})")
+
+def cache_command(argv: List[str]) -> None:
+    """Write a JSON index of the metadata of every plug-in in a directory.
+
+    With --use-cache, plug-ins whose modification time matches the previous
+    index are copied from it instead of being parsed again.
+    """
+    parser = CliArgumentParser(
+        prog=f"{os.path.basename(sys.argv[0])} cache",
+        description="Write a JSON index of the metadata of every plug-in in a directory"
+    )
+    parser.add_argument('--dir', required=True, metavar='DIRECTORY', help='Directory of plug-ins, searched recursively')
+    parser.add_argument('--out', required=True, metavar='PATH', help='Where to write the JSON index')
+    parser.add_argument('--use-cache', metavar='PATH', help='Previous index to reuse unchanged entries from')
+    args = parser.parse_args(argv)
+
+    logging.basicConfig(level=logging.WARNING, format='%(message)s', stream=sys.stderr)
+
+    if not os.path.isdir(args.dir):
+        logger.error(f"Error: Directory '{args.dir}' not found.")
+        sys.exit(EXIT_ERROR)
+
+    previous = {}
+    if args.use_cache:
+        try:
+            previous = load_cache(args.use_cache)
+        except FileNotFoundError:
+            logger.warning(f"Warning: Cache {args.use_cache} not found; parsing every plug-in")
+        except (OSError, ValueError) as e:
+            logger.error(f"Error: Could not read cache {args.use_cache}: {e}")
+            sys.exit(EXIT_ERROR)
+
+    index = {}
+    reused = 0
+    for path in directory_plugin_paths(args.dir, recursive=True):
+        if path.lower().endswith('.zip'):
+            continue
+        relative_path = os.path.relpath(path, args.dir)
+        mtime_ns = plugin_mtime_ns(path)
+
+        cached = previous.get(relative_path)
+        if cached is not None and cached.get('mtime_ns') == mtime_ns:
+            index[relative_path] = cached
+            reused += 1
+            continue
+
+        file_type = detect_file_type(path)
+        try:
+            entries = parse_file_entries(path, file_type) if file_type else []
+        except PiplError as e:
+            logger.warning(f"Warning: Could not parse {path}: {e}")
+            entries = []
+        index[relative_path] = build_cache_entry(mtime_ns, entries)
+
+    write_cache(args.out, args.dir, index)
+    print(f"Indexed {len(index)} plug-ins in {args.dir} to {args.out} "
+          f"({len(index) - reused} parsed, {reused} unchanged)")
+
+# Subcommands are dispatched on the first argument so plain file arguments keep working
+SUBCOMMANDS = {
+    'cache': cache_command,
+    'dump': dump_command,
+    'patch': patch_command
+}
+
+# How often --watch checks the input for changes, in seconds
+WATCH_POLL_INTERVAL = 0.5
+
+def watch_signature(path: str) -> Optional[Tuple[int, int]]:
+    """Return the (latest mtime, total size) of a file or bundle, or None while it doesn't exist."""
+    try:
+        if not os.path.isdir(path):
+            stat = os.stat(path)
+            return stat.st_mtime_ns, stat.st_size
+
+        latest, total = 0, 0
+        for root, _, files in os.walk(path):
+            for name in files:
+                stat = os.stat(os.path.join(root, name))
+                latest, total = max(latest, stat.st_mtime_ns), total + stat.st_size
+        return latest, total
+    except OSError:
+        # Linkers often delete and recreate the output, so a missing file is expected mid-build
+        return None
+
+def watch_file(input_file: str, args: argparse.Namespace) -> None:
+    """Re-run extraction whenever the input changes, until interrupted with Ctrl-C."""
+    last_signature = None
+    first_run = True
+    try:
+        while True:
+            signature = watch_signature(input_file)
+            if signature is not None and signature != last_signature:
+                # Wait for the writer to finish before parsing a half-written file
+                time.sleep(WATCH_POLL_INTERVAL)
+                if watch_signature(input_file) != signature:
+                    continue
+                if not first_run:
+                    print(f"\n--- {input_file} changed at {time.strftime('%H:%M:%S')} ---")
+                process_file(input_file, args)
+                last_signature = signature
+                first_run = False
+            elif signature is None and last_signature is not None:
+                logger.info(f"Waiting for {input_file} to reappear...")
+                last_signature = None
+            time.sleep(WATCH_POLL_INTERVAL)
+    except KeyboardInterrupt:
+        pass
+
+def collect_resource_type_summaries(results: List[ProcessResult]) -> List[ResourceTypeSummary]:
+    """Merge the resource map summaries of every parsed resource fork in a batch, by type."""
+    merged: Dict[str, ResourceTypeSummary] = {}
+
+    for result in results:
+        if not result.ok or not any(entry.format == 'resource_fork' for entry in result.entries):
+            continue
+        rsrc_path = find_rsrc_in_plugin(result.path) if os.path.isdir(result.path) else result.path
+        # Members of ZIP archives and bare 8BIM streams have no resource map to read
+        if not rsrc_path or not os.path.isfile(rsrc_path):
+            continue
+        try:
+            summaries = ResourceForkParser(rsrc_path).summarize_resource_types()
+        except (OSError, ValueError, PiplError):
+            continue
+        for summary in summaries:
+            total = merged.setdefault(summary.resource_type, ResourceTypeSummary(summary.resource_type, 0, 0))
+            total.resource_count += summary.resource_count
+            total.data_bytes += summary.data_bytes
+
+    return list(merged.values())
+
+def collect_version_stats(results: List[ProcessResult]) -> Dict[str, object]:
+    """Aggregate the effect versions and resource maps of a batch of parsed files for --stats."""
+    versions = [entry.version_info for result in results if result.ok
+                for entry in result.entries if entry.version_info]
+    # VersionInfo isn't hashable, so count by the version string in version order
+    histogram = Counter(str(version) for version in sorted(versions))
+    resource_types = collect_resource_type_summaries(results)
+    most_common_type = ResourceTypeSummary.most_common_type(resource_types)
+
+    return {
+        'files_scanned': len(results),
+        'files_parsed': sum(1 for result in results if result.ok),
+        'versions': [{'version': version, 'count': count} for version, count in histogram.items()],
+        'release': sum(1 for version in versions if version.stage == Stage.RELEASE),
+        'pre_release': sum(1 for version in versions if version.stage != Stage.RELEASE),
+        'resources': total_resource_count(resource_types),
+        'resource_data_bytes': total_data_bytes(resource_types),
+        'most_common_resource_type': most_common_type.resource_type if most_common_type else None
+    }
+
+def print_version_stats(stats: Dict[str, object], args: argparse.Namespace) -> None:
+    """Print the --stats summary as a table, or as a JSON object with a `stats` key."""
+    if args.format == 'json':
+        print(json.dumps({'stats': stats}, indent=2))
+        return
+
+    print(f"Scanned {stats['files_scanned']} files, {stats['files_parsed']} parsed successfully")
+    if stats['versions']:
+        width = max(len('Version'), *(len(row['version']) for row in stats['versions']))
+        print(f"\n{'Version':<{width}} {'Count':>5}")
+        for row in stats['versions']:
+            print(f"{row['version']:<{width}} {row['count']:>5}")
+    print(f"\nRelease: {stats['release']}, pre-release: {stats['pre_release']}")
+    if stats['resources']:
+        print(f"Resources: {stats['resources']} ({stats['resource_data_bytes']} bytes), "
+              f"most common type {stats['most_common_resource_type']!r}")
+
+def positive_seconds(text: str) -> float:
+    """argparse type for a duration that must be greater than zero."""
+    try:
+        value = float(text)
+    except ValueError:
+        raise argparse.ArgumentTypeError(f"invalid number of seconds: {text!r}") from None
+    if not value > 0:
+        raise argparse.ArgumentTypeError(f"must be greater than 0, got {text}")
+    return value
+
+def positive_int(text: str) -> int:
+    """argparse type for a count that must be at least 1."""
+    try:
+        value = int(text)
+    except ValueError:
+        raise argparse.ArgumentTypeError(f"invalid integer: {text!r}") from None
+    if value < 1:
+        raise argparse.ArgumentTypeError(f"must be at least 1, got {text}")
+    return value
+
+def build_arg_parser() -> CliArgumentParser:
+    """Build the parser for the main command (subcommands parse their own arguments)."""
+    parser = CliArgumentParser(
         description="Extract and decompile Adobe After Effects PIPL resources",
         formatter_class=argparse.RawDescriptionHelpFormatter,
         epilog="""
@@ -151,53 +1297,475 @@ Examples:
   %(prog)s plugin.aex -o plugin.r               # Extract from Windows .aex file
   %(prog)s plugin.plugin -o plugin.r            # Extract from macOS .plugin bundle
   %(prog)s plugin.rsrc --info                   # Show plugin information only
+  %(prog)s dump --key eVER plugin.rsrc          # Hex dump one property's raw bytes
+  %(prog)s patch --version 2.0 in.rsrc out.rsrc  # Write a new effect version to a copy
         """
     )
 
     parser.add_argument(
-        'input_file',
-        help='Input file (.rsrc, .rcp, .aex, or .plugin bundle)'
+        'input_files',
+        nargs='*',
+        metavar='input_file',
+        help='Input files (.rsrc, .rcp, .aex, .dll, .dylib, or .plugin bundle) or directories of plugins'
+    )
+
+    parser.add_argument(
+        '--zip',
+        action='append',
+        metavar='ARCHIVE',
+        help='Parse the .rsrc and .aex files inside a ZIP archive without extracting it (can be repeated)'
+    )
+
+    parser.add_argument(
+        '--recursive',
+        action='store_true',
+        help='Search input directories recursively, including the ZIP archives found in them'
     )
 
     parser.add_argument(
         '--force-type',
-        choices=['rsrc', 'rcp', 'aex', 'plugin'],
-        help='Force file type detection (rsrc, rcp, aex, or plugin)'
+        choices=['rsrc', 'rcp', 'aex', 'plugin', 'macho'],
+        help='Force file type detection (rsrc, rcp, aex, plugin, or macho)'
+    )
+
+    parser.add_argument(
+        '--arch',
+        choices=['arm64', 'x86_64'],
+        help='Architecture slice to read from a universal Mach-O binary'
+    )
+
+    parser.add_argument(
+        '--list-properties',
+        action='store_true',
+        help='Print the signature, key and length of every PiPL property without decoding values'
+    )
+
+    parser.add_argument(
+        '--signature',
+        action='append',
+        metavar='SIG',
+        help='Only report properties with this vendor signature, as an OSType (e.g. 8BIM) or hex; '
+             'repeat to allow several'
+    )
+
+    parser.add_argument(
+        '--ae-effects-only',
+        action='store_true',
+        help='Skip PiPLs whose Kind is not an After Effects effect (eFKT), e.g. AEGPs or Photoshop filters'
     )
 
+    parser.add_argument(
+        '--strict',
+        action='store_true',
+        help='Fail when a file is structurally valid as more than one format instead of trying each in turn'
+    )
+
+    parser.add_argument(
+        '--resource-id',
+        type=int,
+        metavar='N',
+        help='Only show the PiPL resource with this ID when a file contains several'
+    )
+
+    parser.add_argument(
+        '--timeout',
+        type=positive_seconds,
+        metavar='SECONDS',
+        help='Give up with exit code 5 if parsing a file takes longer than SECONDS'
+    )
+
+    parser.add_argument(
+        '--max-properties',
+        type=positive_int,
+        metavar='N',
+        default=MAX_PIPL_PROPERTIES,
+        help=f'Reject PiPLs with more than N properties as corrupt (default {MAX_PIPL_PROPERTIES})'
+    )
+
+    parser.add_argument(
+        '--report-format-detection-confidence',
+        action='store_true',
+        help='Print a 0-100 confidence score for each candidate binary format'
+    )
+
+    parser.add_argument(
+        '--watch',
+        action='store_true',
+        help='Re-run extraction every time the input file changes (Ctrl-C to stop)'
+    )
+
+    parser.add_argument(
+        '--diff',
+        metavar='NEW_RSRC',
+        help='Compare the resource map of the input .rsrc file against NEW_RSRC and exit'
+    )
+
+    parser.add_argument(
+        '--format',
+        choices=['text', 'json', 'toml'],
+        default='text',
+        help='Output format: decoded properties as text, a JSON summary of each PiPL, '
+             'or the effect version as a TOML [plugin] table'
+    )
+
+    parser.add_argument(
+        '--stats',
+        action='store_true',
+        help='Print a summary of the versions found across all input files instead of each file\'s PiPL'
+    )
+
+    parser.add_argument(
+        '--output-template',
+        metavar='TEMPLATE',
+        help='Print each plugin as TEMPLATE with {version}, {subversion}, {bugversion}, {stage}, {build}, '
+             '{raw}, {name} and {path} substituted, e.g. "{path}: {version}.{subversion}.{bugversion}"'
+    )
+
+    parser.add_argument(
+        '--list',
+        action='store_true',
+        help='Print a table of every resource in each input .rsrc file and exit'
+    )
+
+    parser.add_argument(
+        '--list-types',
+        action='store_true',
+        help='Print each resource type in each input .rsrc file with its resource count, IDs and names and exit'
+    )
+
+    parser.add_argument(
+        '--color-severity',
+        action='store_true',
+        help='Color each plugin green (release, GPU), yellow (release, not thread-safe) '
+             'or red (pre-release) when writing to a terminal'
+    )
+
+    parser.add_argument(
+        '--no-color',
+        action='store_true',
+        help='Never use ANSI colors (also disabled by a non-empty NO_COLOR environment variable or a non-terminal stdout)'
+    )
+
+    parser.add_argument(
+        '-v', '--verbose',
+        action='store_true',
+        help='Print additional decoded details such as supported pixel modes'
+    )
+
+    parser.add_argument(
+        '-q', '--quiet',
+        action='store_true',
+        help='Suppress informational output and print only the effect version string'
+    )
+
+    parser.add_argument(
+        '--version-format',
+        choices=['default', 'semver'],
+        default='default',
+        help='How to print effect versions: as "1.5.0 Beta (Build 3)" or as a SemVer string like 1.5.0-beta.3'
+    )
+
+    parser.add_argument(
+        '--min-version',
+        metavar='VERSION',
+        help='Exit with code 4 if the effect version is below VERSION, e.g. "1.5.0 Release"'
+    )
+
+    parser.add_argument(
+        '--check-compat',
+        metavar='AE_VERSION',
+        help='Exit with code 4 unless each plugin is compatible with this host version (same major, not newer)'
+    )
+
+    parser.add_argument(
+        '--check-pipl-version',
+        metavar='EXPECTED_HEX',
+        help='Exit with code 4 if the raw encoded eVER value differs from EXPECTED_HEX, e.g. 0x05380001'
+    )
+
+    parser.add_argument(
+        '--write-lockfile',
+        metavar='PATH',
+        help='Write a JSON lockfile with the checksum and version of every scanned plugin'
+    )
+
+    parser.add_argument(
+        '--check-lockfile',
+        metavar='PATH',
+        help='Fail if any scanned plugin changed version or checksum since the lockfile was written'
+    )
+
+    parser.add_argument(
+        '--emit-cmake-version-file',
+        metavar='PATH',
+        help='Write a CMake file setting PLUGIN_VERSION variables from the effect version'
+    )
+
+    parser.add_argument(
+        '--base64-pipl',
+        metavar='STRING',
+        help='Parse base64-encoded PiPL data (as stored in JSON or TOML configs) instead of input files'
+    )
+
+    parser.add_argument(
+        '--generate-completion',
+        choices=COMPLETION_SHELLS,
+        help=argparse.SUPPRESS
+    )
+
+    parser.add_argument(
+        '--print-sdk-changelog',
+        action='store_true',
+        help='Print known PiPL changes across After Effects SDK versions and exit'
+    )
+
+    return parser
+
+def main():
+    if len(sys.argv) > 1 and sys.argv[1] in SUBCOMMANDS:
+        SUBCOMMANDS[sys.argv[1]](sys.argv[2:])
+        return
+
+    parser = build_arg_parser()
     args = parser.parse_args()
 
-    # Check input file
-    if not os.path.exists(args.input_file):
-        print(f"Error: Input file '{args.input_file}' not found.")
-        sys.exit(1)
+    # Keep stdout machine-readable when printing JSON
+    logging.basicConfig(
+        # --stats replaces the per-file output, including the progress messages
+        level=logging.ERROR if args.quiet else logging.WARNING if args.stats else logging.INFO,
+        format='%(message)s',
+        stream=sys.stdout if args.format == 'text' else sys.stderr
+    )
+
+    if args.signature:
+        try:
+            args.signature = [parse_property_key(text, 'Signature') for text in args.signature]
+        except ValueError as e:
+            parser.error(f"--signature: {e}")
+
+    if args.output_template is not None:
+        for name in unknown_template_placeholders(args.output_template):
+            print(f"Warning: --output-template: unknown placeholder {{{name}}} will be printed as-is", file=sys.stderr)
+
+    if args.generate_completion:
+        print(generate_completion(args.generate_completion, parser, list(SUBCOMMANDS),
+                                  [extension.lstrip('.') for extension in PLUGIN_EXTENSIONS]), end='')
+        return
+
+    if args.print_sdk_changelog:
+        print(AE_SDK_CHANGELOG, end='')
+        return
+
+    if args.base64_pipl:
+        try:
+            properties = parse_pipl_from_base64(args.base64_pipl)
+        except PiplError as e:
+            logger.error(f"Error: --base64-pipl: {e}")
+            sys.exit(EXIT_ERROR)
+        entries = [PiplEntry.from_properties(properties, 'base64')] if properties else []
+        if not entries or output_entries('<base64>', entries, args) is None:
+            sys.exit(EXIT_ERROR)
+        return
+
+    if not args.input_files and not args.zip:
+        parser.error("the following arguments are required: input_files")
+
+    if args.watch:
+        if len(args.input_files) != 1:
+            parser.error("--watch takes exactly one input file")
+        watch_file(args.input_files[0], args)
+        return
+
+    if args.diff:
+        if len(args.input_files) != 1:
+            parser.error("--diff takes exactly one input file")
+        input_file = args.input_files[0]
+        for path in (input_file, args.diff):
+            if not os.path.exists(path):
+                logger.error(f"Error: Input file '{path}' not found.")
+                sys.exit(EXIT_ERROR)
+        try:
+            with open(input_file, 'rb') as old_file, open(args.diff, 'rb') as new_file:
+                diff = PiplForkDiff.compare(old_file.read(), new_file.read())
+        except (OSError, ValueError, PiplError) as e:
+            logger.error(f"Error: Could not compare {input_file} with {args.diff}: {e}")
+            sys.exit(EXIT_ERROR)
+        print(f"Comparing {input_file} -> {args.diff}")
+        print(diff.format_stat())
+        return
 
-    # Detect file type
-    if args.force_type:
-        file_type = args.force_type
+    if args.list or args.list_types:
+        failed = False
+        for input_file in args.input_files:
+            if not os.path.isfile(input_file):
+                logger.error(f"Error: Input file '{input_file}' not found.")
+                failed = True
+                continue
+            print(f"Resource {'types' if args.list_types else 'map'} of {input_file}:")
+            try:
+                data = read_resource_fork(input_file)
+                if data is None:
+                    raise ValueError("AppleDouble file has no resource fork")
+                if args.list_types:
+                    entries = ResourceForkParser.from_bytes(data).get_resource_entries()
+                    print(f"{'Type':<6} {'Count':>5}  Resources")
+                    for summary in summarize_resource_types(entries):
+                        resources = ', '.join(f"{entry.resource_id} {entry.name!r}" if entry.name is not None
+                                              else str(entry.resource_id)
+                                              for entry in entries if entry.resource_type == summary.resource_type)
+                        print(f"{summary.resource_type!r:<6} {summary.resource_count:>5}  {resources}")
+                else:
+                    dump_resource_map_ascii(data, sys.stdout)
+            except (ValueError, PiplError) as e:
+                logger.error(f"Error: Could not read resource map of '{input_file}': {e}")
+                failed = True
+        if failed:
+            sys.exit(EXIT_ERROR)
+        return
+
+    if args.emit_cmake_version_file and len(args.input_files) != 1:
+        parser.error("--emit-cmake-version-file takes exactly one input file")
+
+    min_version = None
+    if args.min_version:
+        try:
+            min_version = VersionInfo.from_str(args.min_version)
+        except ValueError as e:
+            print(f"Error: --min-version: {e}", file=sys.stderr)
+            sys.exit(EXIT_ERROR)
+
+    host_version = None
+    if args.check_compat:
+        try:
+            host_version = VersionInfo.from_str(args.check_compat)
+        except ValueError as e:
+            print(f"Error: --check-compat: {e}", file=sys.stderr)
+            sys.exit(EXIT_ERROR)
+
+    expected_encoded_version = None
+    if args.check_pipl_version:
+        try:
+            expected_encoded_version = int(args.check_pipl_version, 16)
+        except ValueError:
+            print(f"Error: --check-pipl-version: Invalid hex value: {args.check_pipl_version!r}", file=sys.stderr)
+            sys.exit(EXIT_ERROR)
+
+    input_files = expand_input_paths(args.input_files, args.recursive) + (args.zip or [])
+    archives = [input_file for input_file in input_files if is_zip_archive(input_file)]
+    if archives and (args.write_lockfile or args.check_lockfile):
+        parser.error("lockfiles can't be used with ZIP archives")
+
+    if len(input_files) == 1 and not archives and not args.stats:
+        printed = [process_file(input_files[0], args)]
     else:
-        file_type = detect_file_type(args.input_file)
+        # Parse everything first so errors are reported together after the output
+        processed = []
+        for input_file in input_files:
+            if is_zip_archive(input_file):
+                processed.extend(run_with_timeout(args.timeout, parse_zip_archive, input_file, args))
+            else:
+                processed.append(run_with_timeout(args.timeout, parse_input_file, input_file, args))
+        if args.stats:
+            printed = processed
+            print_version_stats(collect_version_stats(processed), args)
+        else:
+            printed = [print_result(result, args, batch=True) if result.ok else result for result in processed]
 
-    if not file_type:
-        print(f"Error: Could not detect file type for '{args.input_file}'.")
-        print("Use --force-type to specify the file type manually.")
-        sys.exit(1)
+        errors = [result for result in processed if not result.ok]
+        if errors:
+            print(f"\n{len(errors)} of {len(processed)} files could not be read:", file=sys.stderr)
+            for result in errors:
+                print(f"  {result.error}", file=sys.stderr)
 
-    print(f"Detected file type: {file_type}")
+    results = [(result.path, result.entries) for result in printed]
+    failures = [result for result in printed if not result.ok]
+    failed = bool(failures)
 
-    # Parse the file
-    print(f"Parsing {args.input_file}...")
-    properties = parse_file(args.input_file, file_type)
+    # Every decoded version, tagged with where it came from
+    versions = [TaggedVersionInfo(input_file, entry.resource_id, entry.version_info)
+                for input_file, entries in results for entry in entries if entry.version_info]
+    unversioned = [input_file for input_file, _ in results
+                   if not any(version.path == input_file for version in versions)]
 
-    if not properties:
-        print("No PIPL properties found. Exiting.")
-        sys.exit(1)
+    if args.emit_cmake_version_file and not failed:
+        if not versions:
+            logger.error(f"No effect version (eVER) found in {results[0][0]}.")
+            sys.exit(EXIT_VERSION_NOT_FOUND)
+        write_cmake_version_file(args.emit_cmake_version_file, versions[0].version, versions[0].path)
+        logger.info(f"Wrote CMake version file to {args.emit_cmake_version_file}")
 
-    print(f"Found {len(properties)} PIPL properties: ")
+    if min_version and not failed:
+        for input_file in unversioned:
+            logger.error(f"No effect version (eVER) found in {input_file}.")
+            sys.exit(EXIT_VERSION_NOT_FOUND)
+        too_old = [version for version in versions if version.version < min_version]
+        for version in too_old:
+            print(f"{version.location}: requires >= {min_version}, found {version.version}", file=sys.stderr)
+        if too_old:
+            sys.exit(EXIT_REQUIREMENT_NOT_MET)
 
-    # Generate .r file
-    generator = RGenerator(properties)
-    generator.print_info()
+    if host_version and not failed:
+        for input_file in unversioned:
+            logger.error(f"No effect version (eVER) found in {input_file}.")
+            sys.exit(EXIT_VERSION_NOT_FOUND)
+        host_label = f"{host_version.version}.{host_version.subversion}.{host_version.bugversion}"
+        incompatible = False
+        for version in versions:
+            plugin_version = version.version
+            if plugin_version.is_compatible_with(host_version):
+                logger.info(f"{version.location}: {plugin_version} is compatible with host {host_label}")
+            elif plugin_version.version != host_version.version:
+                print(f"{version.location}: incompatible, major version {plugin_version.version} "
+                      f"does not match host major version {host_version.version}", file=sys.stderr)
+                incompatible = True
+            else:
+                print(f"{version.location}: incompatible, {plugin_version} is newer than host {host_label}",
+                      file=sys.stderr)
+                incompatible = True
+        if incompatible:
+            sys.exit(EXIT_REQUIREMENT_NOT_MET)
+
+    if expected_encoded_version is not None and not failed:
+        mismatch = False
+        for input_file, entries in results:
+            version_data = next((prop.data for entry in entries for prop in entry.properties
+                                 if normalize_property_type(prop.property_type) == 'eVER' and len(prop.data) >= 4), None)
+            if version_data is None:
+                logger.error(f"No effect version (eVER) found in {input_file}.")
+                sys.exit(EXIT_VERSION_NOT_FOUND)
+            encoded_version = struct.unpack('>I', version_data[:4])[0]
+            if encoded_version != expected_encoded_version:
+                print(f"{input_file}: expected eVER {expected_encoded_version:#010x}, "
+                      f"found {encoded_version:#010x} ({extract_pf_version(encoded_version)})", file=sys.stderr)
+                mismatch = True
+        if mismatch:
+            sys.exit(EXIT_REQUIREMENT_NOT_MET)
+
+    # Inputs that failed to parse may not exist, so they are left out of the lockfile
+    scanned_paths = [result.path for result in printed if result.ok]
+
+    if args.write_lockfile:
+        try:
+            write_lockfile(args.write_lockfile, scanned_paths, versions)
+        except OSError as e:
+            logger.error(f"Error: Could not write lockfile '{args.write_lockfile}': {e}")
+            sys.exit(EXIT_ERROR)
+        logger.info(f"Wrote lockfile for {len(scanned_paths)} plugins to {args.write_lockfile}")
+
+    if args.check_lockfile:
+        try:
+            problems = check_lockfile(args.check_lockfile, scanned_paths, versions)
+        except (OSError, json.JSONDecodeError) as e:
+            logger.error(f"Error: Could not read lockfile '{args.check_lockfile}': {e}")
+            sys.exit(EXIT_ERROR)
+        for problem in problems:
+            logger.error(f"Lockfile mismatch: {problem}")
+        if problems:
+            sys.exit(EXIT_ERROR)
+        logger.info(f"All {len(scanned_paths)} plugins match {args.check_lockfile}")
+
+    if failed:
+        sys.exit(failures[0].exit_code)
 
 if __name__ == '__main__':
     main()
\ No newline at end of file
@@ -15,6 +15,16 @@ class AexAnalyzer:
         self.resource_table_offset = 0
         self._load_file()
 
+    @classmethod
+    def from_bytes(cls, data: bytes, file_path: str = '<memory>') -> 'AexAnalyzer':
+        """Create an analyzer over PE data that is already in memory."""
+        analyzer = cls.__new__(cls)
+        analyzer.file_path = file_path
+        analyzer.data = data
+        analyzer.pe_header_offset = 0
+        analyzer.resource_table_offset = 0
+        return analyzer
+
     def _load_file(self) -> None:
         """Load the entire AEX file into memory."""
         try:
@@ -116,6 +126,89 @@ class AexAnalyzer:
 
         return None
 
+    def _read_resource_name(self, section: Dict, offset: int) -> str:
+        """Read a length-prefixed UTF-16LE resource name relative to the resource section."""
+        name_offset = section['raw_offset'] + offset
+        length = self._read_uint16(name_offset)
+        raw_name = self.data[name_offset + 2:name_offset + 2 + length * 2]
+        return raw_name.decode('utf-16-le', errors='ignore')
+
+    def _read_resource_directory(self, section: Dict, offset: int) -> List[Dict]:
+        """Read the entries of an IMAGE_RESOURCE_DIRECTORY at a section-relative offset."""
+        entries = []
+        directory_offset = section['raw_offset'] + offset
+
+        if directory_offset + 16 > len(self.data):
+            return entries
+
+        num_named = self._read_uint16(directory_offset + 12)
+        num_ids = self._read_uint16(directory_offset + 14)
+
+        for i in range(num_named + num_ids):
+            entry_offset = directory_offset + 16 + (i * 8)  # Each entry is 8 bytes
+            if entry_offset + 8 > len(self.data):
+                break
+
+            name_field = self._read_uint32(entry_offset)
+            data_field = self._read_uint32(entry_offset + 4)
+
+            # High bit set means the name is a string, otherwise it is a numeric ID
+            if name_field & 0x80000000:
+                name = self._read_resource_name(section, name_field & 0x7FFFFFFF)
+            else:
+                name = name_field
+
+            entries.append({
+                'name': name,
+                'is_directory': bool(data_field & 0x80000000),
+                'offset': data_field & 0x7FFFFFFF
+            })
+
+        return entries
+
+    def find_pipl_resources(self) -> List[Dict]:
+        """Walk the PE resource directory and return the raw data of every PiPL resource."""
+        resources = []
+        section = self._find_resource_section()
+
+        if not section:
+            return resources
+
+        # Resource compilers store custom type names upper-cased, so 'PiPL' becomes 'PIPL'
+        for type_entry in self._read_resource_directory(section, 0):
+            if not (isinstance(type_entry['name'], str) and type_entry['name'].upper() == 'PIPL'):
+                continue
+            if not type_entry['is_directory']:
+                continue
+
+            for id_entry in self._read_resource_directory(section, type_entry['offset']):
+                if not id_entry['is_directory']:
+                    continue
+
+                for lang_entry in self._read_resource_directory(section, id_entry['offset']):
+                    if lang_entry['is_directory']:
+                        continue
+
+                    # IMAGE_RESOURCE_DATA_ENTRY holds an RVA, translate it to a file offset
+                    data_entry_offset = section['raw_offset'] + lang_entry['offset']
+                    if data_entry_offset + 16 > len(self.data):
+                        continue
+
+                    data_rva = self._read_uint32(data_entry_offset)
+                    data_size = self._read_uint32(data_entry_offset + 4)
+                    data_offset = data_rva - section['virtual_address'] + section['raw_offset']
+
+                    if data_offset < 0 or data_offset + data_size > len(self.data):
+                        continue
+
+                    resources.append({
+                        'id': id_entry['name'],
+                        'offset': data_offset,
+                        'data': self.data[data_offset:data_offset + data_size]
+                    })
+
+        return resources
+
     def _search_for_pipl_data(self) -> List[Dict]:
         """Search for PIPL data in the entire file."""
         pipl_blocks = []
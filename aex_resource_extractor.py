@@ -6,9 +6,13 @@ properties from a Windows `.aex` plugin's resource section. Standalone CLI
 and extra analysis utilities were removed to keep the repository minimal.
 """
 
+import logging
 import struct
+from typing import List
 from aex_analyzer import AexAnalyzer
-from pipl_types import PiplProperty
+from pipl_types import PiplProperty, normalize_aex_property
+
+logger = logging.getLogger(__name__)
 
 class AexResourceExtractor:
     """Extract PIPL data from AEX resource section."""
@@ -18,9 +22,24 @@ class AexResourceExtractor:
         self.resource_data = None
         self._load_resources()
 
+    @classmethod
+    def from_bytes(cls, data: bytes, file_path: str = '<memory>') -> 'AexResourceExtractor':
+        """Create an extractor over an .aex file that is already in memory."""
+        extractor = cls.__new__(cls)
+        extractor.analyzer = AexAnalyzer.from_bytes(data, file_path)
+        extractor.resource_data = None
+        extractor._load_resources()
+        return extractor
+
     def _load_resources(self):
         """Load resource section data."""
-        self.resource_data = self.analyzer.extract_potential_rcp_data()
+        # Prefer the PiPL resource located through the resource directory, and
+        # fall back to scanning the whole resource section when it can't be found
+        pipl_resources = self.analyzer.find_pipl_resources()
+        if pipl_resources:
+            self.resource_data = pipl_resources[0]['data']
+        else:
+            self.resource_data = self.analyzer.extract_potential_rcp_data()
 
     def _find_pipl_data_in_resources(self):
         """Find PIPL data in the resource section."""
@@ -44,91 +63,74 @@ class AexResourceExtractor:
         # Extract PIPL data from MIB8 start to end of section
         return self.resource_data[pipl_start:]
 
-    def _normalize_aex_property(self, prop_type_bytes: bytes, prop_data: bytes) -> tuple[str, bytes]:
-        """Normalize AEX property type (reverse 4CC) and convert little-endian values to big-endian bytes.
-        This ensures downstream decoders (expecting big-endian like .rcp/.rsrc) behave consistently.
-        """
-        # Reverse 4CC like b'RVPe' -> 'ePVR'
-        try:
-            corrected_type = prop_type_bytes[::-1].decode('ascii', errors='ignore')
-        except Exception:
-            corrected_type = prop_type_bytes[::-1].hex()
-
-        # For known numeric properties, convert little-endian to big-endian byte order
-        if corrected_type in ('ePVR', 'eSVR'):
-            # Two 16-bit values in little-endian → repack as big-endian
-            if len(prop_data) >= 4:
-                major_le, minor_le = struct.unpack('<HH', prop_data[:4])
-                prop_data = struct.pack('>HH', major_le, minor_le)
-        elif corrected_type in ('eVER', 'eINF', 'eGLO', 'eGL2', 'aeFL'):
-            # Single 32-bit value little-endian → big-endian
-            if len(prop_data) >= 4:
-                value_le = struct.unpack('<I', prop_data[:4])[0]
-                prop_data = struct.pack('>I', value_le)
-        # Other types (strings/entry points) are left as-is
-
-        return corrected_type, prop_data
+    def extract_pipl_bytes(self) -> List[bytes]:
+        """Return the raw PiPL data in the resource section without parsing its properties."""
+        pipl_data = self._find_pipl_data_in_resources()
+        return [bytes(pipl_data)] if pipl_data else []
 
     def extract_pipl_properties(self):
         """Extract PIPL properties from the resource section."""
-        pipl_data = self._find_pipl_data_in_resources()
-        if not pipl_data:
-            return []
-
         properties = []
-        offset = 0
+        for pipl_data in self.extract_pipl_bytes():
+            properties.extend(parse_windows_pipl_data(pipl_data))
+        return properties
 
-        print(f"Analyzing {len(pipl_data)} bytes of PIPL data...")
+    # Note: Standalone CLI removed. Use via `ae_pipl_extractor.py`.
 
-        while offset < len(pipl_data) - 12:
-            # Look for MIB8 signature
-            if pipl_data[offset:offset+4] == b'MIB8':
-                try:
-                    # Read property type (4 bytes after MIB8)
-                    prop_type = pipl_data[offset+4:offset+8]
+def parse_windows_pipl_data(pipl_data: bytes) -> List[PiplProperty]:
+    """Parse little-endian MIB8 properties as compiled into Windows resources."""
+    properties = []
+    offset = 0
 
-                    # Skip null padding (usually 4 bytes)
-                    length_offset = offset + 8
-                    while (length_offset < len(pipl_data) and
-                           length_offset < offset + 16 and
-                           pipl_data[length_offset] == 0):
-                        length_offset += 1
+    logger.info(f"Analyzing {len(pipl_data)} bytes of PIPL data...")
 
-                    # Read length (little-endian for Windows resources)
-                    if length_offset + 4 <= len(pipl_data):
-                        length = struct.unpack('<I', pipl_data[length_offset:length_offset+4])[0]
+    while offset < len(pipl_data) - 12:
+        # Look for MIB8 signature
+        if pipl_data[offset:offset+4] == b'MIB8':
+            try:
+                # Read property type (4 bytes after MIB8)
+                prop_type = pipl_data[offset+4:offset+8]
 
-                        # Validate length
-                        if length > 0 and length < 10000:
-                            data_start = length_offset + 4
+                # Skip null padding (usually 4 bytes)
+                length_offset = offset + 8
+                while (length_offset < len(pipl_data) and
+                       length_offset < offset + 16 and
+                       pipl_data[length_offset] == 0):
+                    length_offset += 1
 
-                            if data_start + length <= len(pipl_data):
-                                prop_data = pipl_data[data_start:data_start + length]
+                # Read length (little-endian for Windows resources)
+                if length_offset + 4 <= len(pipl_data):
+                    length = struct.unpack('<I', pipl_data[length_offset:length_offset+4])[0]
 
-                                # Normalize type and data endianness for AEX
-                                corrected_type, corrected_data = self._normalize_aex_property(prop_type, prop_data)
+                    # Validate length
+                    if length > 0 and length < 10000:
+                        data_start = length_offset + 4
 
-                                properties.append(PiplProperty(
-                                    property_type=corrected_type,
-                                    data=corrected_data,
-                                    length=len(corrected_data)
-                                ))
+                        if data_start + length <= len(pipl_data):
+                            prop_data = pipl_data[data_start:data_start + length]
 
-                                print(f"Found property '{corrected_type}' length={len(corrected_data)} at offset=0x{offset:04x}")
+                            # Normalize type and data endianness for AEX
+                            corrected_type, corrected_data = normalize_aex_property(prop_type, prop_data)
 
-                                # Move to next property
-                                offset = data_start + length
-                                # Align to next MIB8
-                                while (offset < len(pipl_data) - 4 and
-                                       pipl_data[offset:offset+4] != b'MIB8'):
-                                    offset += 1
-                                continue
+                            properties.append(PiplProperty(
+                                property_type=corrected_type,
+                                data=corrected_data,
+                                length=len(corrected_data)
+                            ))
 
-                except Exception as e:
-                    print(f"Error parsing property at offset 0x{offset:04x}: {e}")
+                            logger.info(f"Found property '{corrected_type}' length={len(corrected_data)} at offset=0x{offset:04x}")
 
-            offset += 1
+                            # Move to next property
+                            offset = data_start + length
+                            # Align to next MIB8
+                            while (offset < len(pipl_data) - 4 and
+                                   pipl_data[offset:offset+4] != b'MIB8'):
+                                offset += 1
+                            continue
 
-        return properties
+            except Exception as e:
+                logger.warning(f"Error parsing property at offset 0x{offset:04x}: {e}")
+
+        offset += 1
 
-    # Note: Standalone CLI removed. Use via `ae_pipl_extractor.py`.
\ No newline at end of file
+    return properties
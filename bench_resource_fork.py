@@ -0,0 +1,112 @@
+#!/usr/bin/env python3
+"""Benchmark resource fork parsing against a corpus of synthetic resource forks.
+
+Each synthetic fork contains one 'PiPL' resource plus filler resources spread
+over a configurable number of resource types, padded out to the target size.
+Results are reported as throughput in bytes/second per (size, type count) pair.
+"""
+
+import argparse
+import struct
+import sys
+import time
+from typing import Dict, Tuple
+
+from pipl_builder import build_resource_fork
+from resource_fork_parser import ResourceForkParser
+
+FORK_SIZES = [1024, 10 * 1024, 100 * 1024, 1024 * 1024]
+TYPE_COUNTS = [1, 10, 50]
+
+# Minimum acceptable throughput in bytes/second per fork size. The run fails
+# with --check if any measured throughput drops below these values.
+REGRESSION_THRESHOLDS = {
+    1024: 1_000_000,
+    10 * 1024: 1_000_000,
+    100 * 1024: 1_000_000,
+    1024 * 1024: 1_000_000,
+}
+
+def build_pipl_resource() -> bytes:
+    """Build a small big-endian PiPL resource with kind, name and eVER properties."""
+    properties = [
+        (b'kind', b'eFKT'),
+        (b'name', b'\x0cBench Effect'),
+        (b'eVER', struct.pack('>I', 0x000B9801)),
+    ]
+
+    data = struct.pack('>II', 0, len(properties))
+    for key, value in properties:
+        padding = (4 - len(value) % 4) % 4
+        data += b'8BIM' + key + struct.pack('>II', 0, len(value)) + value + b'\x00' * padding
+
+    return data
+
+def build_synthetic_fork(size: int, type_count: int) -> bytes:
+    """Build a fork of approximately `size` bytes spread across `type_count` types."""
+    resources = [(b'PiPL', 16000, build_pipl_resource())]
+
+    filler_types = [f'T{i:03d}'.encode('ascii') for i in range(type_count - 1)]
+    if filler_types:
+        overhead = 256 + 28 + 2 + 8 * type_count + 12 * type_count + 4 * type_count
+        filler_size = max(0, (size - overhead - len(resources[0][2])) // len(filler_types))
+        for resource_type in filler_types:
+            resources.append((resource_type, 128, b'\xa5' * filler_size))
+    else:
+        # A single type: pad the PiPL type with extra resources up to the target size
+        filler_size = max(0, size - 512)
+        resources.append((b'PiPL', 16001, b'\xa5' * filler_size))
+
+    return build_resource_fork(resources)
+
+def run_benchmark(corpus_size: int) -> Dict[Tuple[int, int], float]:
+    """Parse `corpus_size` synthetic forks and return throughput per configuration."""
+    configurations = [(size, types) for size in FORK_SIZES for types in TYPE_COUNTS]
+    forks_per_config = max(1, corpus_size // len(configurations))
+    results = {}
+
+    for size, type_count in configurations:
+        corpus = [build_synthetic_fork(size, type_count) for _ in range(forks_per_config)]
+        total_bytes = sum(len(fork) for fork in corpus)
+
+        start = time.perf_counter()
+        for fork in corpus:
+            entries = ResourceForkParser.from_bytes(fork).parse_pipl_entries()
+            if not entries:
+                raise RuntimeError(f"No PIPL properties parsed from {size}-byte fork")
+        elapsed = time.perf_counter() - start
+
+        results[(size, type_count)] = total_bytes / elapsed if elapsed > 0 else float('inf')
+        print(f"{size:>8} bytes  {type_count:>3} types  {forks_per_config:>4} forks  "
+              f"{results[(size, type_count)] / 1024:>10.1f} KiB/s")
+
+    return results
+
+def main():
+    parser = argparse.ArgumentParser(description="Benchmark resource fork parsing")
+    parser.add_argument(
+        '--corpus-size',
+        type=int,
+        default=1000,
+        help='Total number of synthetic forks to parse (default: 1000)'
+    )
+    parser.add_argument(
+        '--check',
+        action='store_true',
+        help='Exit with a non-zero code if throughput falls below the regression thresholds'
+    )
+    args = parser.parse_args()
+
+    results = run_benchmark(args.corpus_size)
+
+    if args.check:
+        failures = [(size, types, throughput) for (size, types), throughput in results.items()
+                    if throughput < REGRESSION_THRESHOLDS[size]]
+        for size, types, throughput in failures:
+            print(f"Regression: {size} bytes / {types} types at {throughput:.0f} B/s "
+                  f"(threshold {REGRESSION_THRESHOLDS[size]} B/s)")
+        if failures:
+            sys.exit(1)
+
+if __name__ == '__main__':
+    main()
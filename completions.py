@@ -0,0 +1,177 @@
+"""Generate shell completion scripts from the CLI's argparse parser."""
+
+import argparse
+from dataclasses import dataclass
+from typing import List, Optional, Sequence
+
+COMPLETION_SHELLS = ['bash', 'zsh', 'fish', 'powershell']
+
+# Options whose value is a path, identified by their metavar
+PATH_METAVARS = ('PATH', 'NEW_RSRC', 'ARCHIVE')
+
+@dataclass
+class CompletionOption:
+    """One option of the parser, reduced to what completion scripts need."""
+    flags: List[str]
+    help: str
+    takes_value: bool
+    choices: Optional[List[str]]
+    is_path: bool
+
+def _parser_options(parser: argparse.ArgumentParser) -> List[CompletionOption]:
+    """Collect the options of a parser. Hidden options keep their flags but lose their help text."""
+    options = []
+
+    for action in parser._actions:
+        if not action.option_strings:
+            continue
+        help_text = '' if action.help in (None, argparse.SUPPRESS) else action.help
+        options.append(CompletionOption(
+            flags=list(action.option_strings),
+            help=help_text,
+            takes_value=action.nargs != 0,
+            choices=[str(choice) for choice in action.choices] if action.choices else None,
+            is_path=action.metavar in PATH_METAVARS
+        ))
+
+    return options
+
+def _bash(prog: str, options: List[CompletionOption], subcommands: List[str], extensions: Sequence[str]) -> str:
+    function = '_' + ''.join(char if char.isalnum() else '_' for char in prog)
+    flags = ' '.join(flag for option in options for flag in option.flags)
+    pattern = '|'.join(extensions)
+
+    cases = []
+    for option in options:
+        if option.choices:
+            cases.append(f"        {'|'.join(option.flags)})\n"
+                         f"            COMPREPLY=($(compgen -W \"{' '.join(option.choices)}\" -- \"$cur\")); return;;")
+        elif option.is_path:
+            cases.append(f"        {'|'.join(option.flags)})\n"
+                         f"            COMPREPLY=($(compgen -f -- \"$cur\")); return;;")
+        elif option.takes_value:
+            cases.append(f"        {'|'.join(option.flags)})\n"
+                         f"            return;;")
+
+    return f"""# bash completion for {prog}
+shopt -s extglob
+
+{function}() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}"
+    local prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+
+    case "$prev" in
+{chr(10).join(cases)}
+    esac
+
+    if [[ "$cur" == -* ]]; then
+        COMPREPLY=($(compgen -W "{flags}" -- "$cur"))
+    else
+        COMPREPLY=($(compgen -W "{' '.join(subcommands)}" -- "$cur")
+                   $(compgen -f -X '!*.@({pattern})' -- "$cur")
+                   $(compgen -d -- "$cur"))
+    fi
+}}
+
+complete -o filenames -F {function} {prog}
+"""
+
+def _zsh(prog: str, options: List[CompletionOption], subcommands: List[str], extensions: Sequence[str]) -> str:
+    specs = []
+    for option in options:
+        flags = option.flags[0] if len(option.flags) == 1 else '{' + ','.join(option.flags) + '}'
+        help_text = option.help.replace("'", "'\\''").replace('[', '(').replace(']', ')')
+        if option.choices:
+            value = f":value:({' '.join(option.choices)})"
+        elif option.is_path:
+            value = ':path:_files'
+        elif option.takes_value:
+            value = ':value:'
+        else:
+            value = ''
+        specs.append(f"    '{flags}[{help_text}]{value}'")
+
+    specs.append(f"    '1::subcommand:({' '.join(subcommands)})'")
+    specs.append(f"    '*:input file:_files -g \"*.({'|'.join(extensions)})\"'")
+
+    return f"""#compdef {prog}
+
+_arguments -s \\
+{(' ' + chr(92) + chr(10)).join(specs)}
+"""
+
+def _fish(prog: str, options: List[CompletionOption], subcommands: List[str], extensions: Sequence[str]) -> str:
+    lines = [f"# fish completion for {prog}"]
+
+    for subcommand in subcommands:
+        lines.append(f"complete -c {prog} -n '__fish_use_subcommand' -a {subcommand}")
+
+    for option in options:
+        parts = [f"complete -c {prog}"]
+        for flag in option.flags:
+            parts.append(f"-l {flag[2:]}" if flag.startswith('--') else f"-s {flag[1:]}")
+        if option.choices:
+            parts.append(f"-x -a '{' '.join(option.choices)}'")
+        elif option.is_path:
+            parts.append('-r -F')
+        elif option.takes_value:
+            parts.append('-x')
+        if option.help:
+            parts.append("-d '" + option.help.replace("'", "\\'") + "'")
+        lines.append(' '.join(parts))
+
+    globs = ' '.join(f"(__fish_complete_suffix .{extension})" for extension in extensions)
+    lines.append(f"complete -c {prog} -a \"{globs}\"")
+
+    return '\n'.join(lines) + '\n'
+
+def _powershell(prog: str, options: List[CompletionOption], subcommands: List[str],
+                extensions: Sequence[str]) -> str:
+    flags = ', '.join(f"'{flag}'" for option in options for flag in option.flags)
+    choices = '\n'.join(f"        '{flag}' = @({', '.join(repr(choice) for choice in option.choices)})"
+                        for option in options if option.choices for flag in option.flags)
+    patterns = ', '.join(f"'*.{extension}'" for extension in extensions)
+
+    return f"""# PowerShell completion for {prog}
+Register-ArgumentCompleter -Native -CommandName '{prog}' -ScriptBlock {{
+    param($wordToComplete, $commandAst, $cursorPosition)
+
+    $choices = @{{
+{choices}
+    }}
+    $elements = $commandAst.CommandElements
+    $previous = if ($elements.Count -gt 1) {{ $elements[-1].ToString() }} else {{ '' }}
+    if ($wordToComplete -and $elements.Count -gt 1) {{ $previous = $elements[-2].ToString() }}
+
+    if ($choices.ContainsKey($previous)) {{
+        $candidates = $choices[$previous]
+    }} elseif ($wordToComplete -like '-*') {{
+        $candidates = @({flags})
+    }} else {{
+        $candidates = @({', '.join(repr(subcommand) for subcommand in subcommands)})
+        $candidates += Get-ChildItem -Path "$wordToComplete*" -Include {patterns} -Name -ErrorAction SilentlyContinue
+        $candidates += Get-ChildItem -Path "$wordToComplete*" -Directory -Name -ErrorAction SilentlyContinue
+    }}
+
+    $candidates | Where-Object {{ $_ -like "$wordToComplete*" }} | ForEach-Object {{
+        [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterValue', $_)
+    }}
+}}
+"""
+
+GENERATORS = {
+    'bash': _bash,
+    'zsh': _zsh,
+    'fish': _fish,
+    'powershell': _powershell
+}
+
+def generate_completion(shell: str, parser: argparse.ArgumentParser, subcommands: List[str],
+                        extensions: Sequence[str]) -> str:
+    """Return a completion script for the given shell.
+
+    Input files complete to the given extensions (without the dot), and
+    options with choices complete to those choices.
+    """
+    options = [option for option in _parser_options(parser) if '--generate-completion' not in option.flags]
+    return GENERATORS[shell](parser.prog, options, subcommands, extensions)
@@ -0,0 +1,56 @@
+# bash completion for ae_pipl_extractor.py
+shopt -s extglob
+
+_ae_pipl_extractor_py() {
+    local cur="${COMP_WORDS[COMP_CWORD]}"
+    local prev="${COMP_WORDS[COMP_CWORD-1]}"
+
+    case "$prev" in
+        --zip)
+            COMPREPLY=($(compgen -f -- "$cur")); return;;
+        --force-type)
+            COMPREPLY=($(compgen -W "rsrc rcp aex plugin macho" -- "$cur")); return;;
+        --arch)
+            COMPREPLY=($(compgen -W "arm64 x86_64" -- "$cur")); return;;
+        --signature)
+            return;;
+        --resource-id)
+            return;;
+        --timeout)
+            return;;
+        --max-properties)
+            return;;
+        --diff)
+            COMPREPLY=($(compgen -f -- "$cur")); return;;
+        --format)
+            COMPREPLY=($(compgen -W "text json toml" -- "$cur")); return;;
+        --output-template)
+            return;;
+        --version-format)
+            COMPREPLY=($(compgen -W "default semver" -- "$cur")); return;;
+        --min-version)
+            return;;
+        --check-compat)
+            return;;
+        --check-pipl-version)
+            return;;
+        --write-lockfile)
+            COMPREPLY=($(compgen -f -- "$cur")); return;;
+        --check-lockfile)
+            COMPREPLY=($(compgen -f -- "$cur")); return;;
+        --emit-cmake-version-file)
+            COMPREPLY=($(compgen -f -- "$cur")); return;;
+        --base64-pipl)
+            return;;
+    esac
+
+    if [[ "$cur" == -* ]]; then
+        COMPREPLY=($(compgen -W "-h --help --zip --recursive --force-type --arch --list-properties --signature --ae-effects-only --strict --resource-id --timeout --max-properties --report-format-detection-confidence --watch --diff --format --stats --output-template --list --list-types --color-severity --no-color -v --verbose -q --quiet --version-format --min-version --check-compat --check-pipl-version --write-lockfile --check-lockfile --emit-cmake-version-file --base64-pipl --print-sdk-changelog" -- "$cur"))
+    else
+        COMPREPLY=($(compgen -W "cache dump patch" -- "$cur")
+                   $(compgen -f -X '!*.@(rsrc|rcp|aex|dll|dylib|plugin)' -- "$cur")
+                   $(compgen -d -- "$cur"))
+    fi
+}
+
+complete -o filenames -F _ae_pipl_extractor_py ae_pipl_extractor.py
@@ -0,0 +1,38 @@
+# fish completion for ae_pipl_extractor.py
+complete -c ae_pipl_extractor.py -n '__fish_use_subcommand' -a cache
+complete -c ae_pipl_extractor.py -n '__fish_use_subcommand' -a dump
+complete -c ae_pipl_extractor.py -n '__fish_use_subcommand' -a patch
+complete -c ae_pipl_extractor.py -s h -l help -d 'show this help message and exit'
+complete -c ae_pipl_extractor.py -l zip -r -F -d 'Parse the .rsrc and .aex files inside a ZIP archive without extracting it (can be repeated)'
+complete -c ae_pipl_extractor.py -l recursive -d 'Search input directories recursively, including the ZIP archives found in them'
+complete -c ae_pipl_extractor.py -l force-type -x -a 'rsrc rcp aex plugin macho' -d 'Force file type detection (rsrc, rcp, aex, plugin, or macho)'
+complete -c ae_pipl_extractor.py -l arch -x -a 'arm64 x86_64' -d 'Architecture slice to read from a universal Mach-O binary'
+complete -c ae_pipl_extractor.py -l list-properties -d 'Print the signature, key and length of every PiPL property without decoding values'
+complete -c ae_pipl_extractor.py -l signature -x -d 'Only report properties with this vendor signature, as an OSType (e.g. 8BIM) or hex; repeat to allow several'
+complete -c ae_pipl_extractor.py -l ae-effects-only -d 'Skip PiPLs whose Kind is not an After Effects effect (eFKT), e.g. AEGPs or Photoshop filters'
+complete -c ae_pipl_extractor.py -l strict -d 'Fail when a file is structurally valid as more than one format instead of trying each in turn'
+complete -c ae_pipl_extractor.py -l resource-id -x -d 'Only show the PiPL resource with this ID when a file contains several'
+complete -c ae_pipl_extractor.py -l timeout -x -d 'Give up with exit code 5 if parsing a file takes longer than SECONDS'
+complete -c ae_pipl_extractor.py -l max-properties -x -d 'Reject PiPLs with more than N properties as corrupt (default 256)'
+complete -c ae_pipl_extractor.py -l report-format-detection-confidence -d 'Print a 0-100 confidence score for each candidate binary format'
+complete -c ae_pipl_extractor.py -l watch -d 'Re-run extraction every time the input file changes (Ctrl-C to stop)'
+complete -c ae_pipl_extractor.py -l diff -r -F -d 'Compare the resource map of the input .rsrc file against NEW_RSRC and exit'
+complete -c ae_pipl_extractor.py -l format -x -a 'text json toml' -d 'Output format: decoded properties as text, a JSON summary of each PiPL, or the effect version as a TOML [plugin] table'
+complete -c ae_pipl_extractor.py -l stats -d 'Print a summary of the versions found across all input files instead of each file\'s PiPL'
+complete -c ae_pipl_extractor.py -l output-template -x -d 'Print each plugin as TEMPLATE with {version}, {subversion}, {bugversion}, {stage}, {build}, {raw}, {name} and {path} substituted, e.g. "{path}: {version}.{subversion}.{bugversion}"'
+complete -c ae_pipl_extractor.py -l list -d 'Print a table of every resource in each input .rsrc file and exit'
+complete -c ae_pipl_extractor.py -l list-types -d 'Print each resource type in each input .rsrc file with its resource count, IDs and names and exit'
+complete -c ae_pipl_extractor.py -l color-severity -d 'Color each plugin green (release, GPU), yellow (release, not thread-safe) or red (pre-release) when writing to a terminal'
+complete -c ae_pipl_extractor.py -l no-color -d 'Never use ANSI colors (also disabled by a non-empty NO_COLOR environment variable or a non-terminal stdout)'
+complete -c ae_pipl_extractor.py -s v -l verbose -d 'Print additional decoded details such as supported pixel modes'
+complete -c ae_pipl_extractor.py -s q -l quiet -d 'Suppress informational output and print only the effect version string'
+complete -c ae_pipl_extractor.py -l version-format -x -a 'default semver' -d 'How to print effect versions: as "1.5.0 Beta (Build 3)" or as a SemVer string like 1.5.0-beta.3'
+complete -c ae_pipl_extractor.py -l min-version -x -d 'Exit with code 4 if the effect version is below VERSION, e.g. "1.5.0 Release"'
+complete -c ae_pipl_extractor.py -l check-compat -x -d 'Exit with code 4 unless each plugin is compatible with this host version (same major, not newer)'
+complete -c ae_pipl_extractor.py -l check-pipl-version -x -d 'Exit with code 4 if the raw encoded eVER value differs from EXPECTED_HEX, e.g. 0x05380001'
+complete -c ae_pipl_extractor.py -l write-lockfile -r -F -d 'Write a JSON lockfile with the checksum and version of every scanned plugin'
+complete -c ae_pipl_extractor.py -l check-lockfile -r -F -d 'Fail if any scanned plugin changed version or checksum since the lockfile was written'
+complete -c ae_pipl_extractor.py -l emit-cmake-version-file -r -F -d 'Write a CMake file setting PLUGIN_VERSION variables from the effect version'
+complete -c ae_pipl_extractor.py -l base64-pipl -x -d 'Parse base64-encoded PiPL data (as stored in JSON or TOML configs) instead of input files'
+complete -c ae_pipl_extractor.py -l print-sdk-changelog -d 'Print known PiPL changes across After Effects SDK versions and exit'
+complete -c ae_pipl_extractor.py -a "(__fish_complete_suffix .rsrc) (__fish_complete_suffix .rcp) (__fish_complete_suffix .aex) (__fish_complete_suffix .dll) (__fish_complete_suffix .dylib) (__fish_complete_suffix .plugin)"
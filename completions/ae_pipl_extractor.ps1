@@ -0,0 +1,28 @@
+# PowerShell completion for ae_pipl_extractor.py
+Register-ArgumentCompleter -Native -CommandName 'ae_pipl_extractor.py' -ScriptBlock {
+    param($wordToComplete, $commandAst, $cursorPosition)
+
+    $choices = @{
+        '--force-type' = @('rsrc', 'rcp', 'aex', 'plugin', 'macho')
+        '--arch' = @('arm64', 'x86_64')
+        '--format' = @('text', 'json', 'toml')
+        '--version-format' = @('default', 'semver')
+    }
+    $elements = $commandAst.CommandElements
+    $previous = if ($elements.Count -gt 1) { $elements[-1].ToString() } else { '' }
+    if ($wordToComplete -and $elements.Count -gt 1) { $previous = $elements[-2].ToString() }
+
+    if ($choices.ContainsKey($previous)) {
+        $candidates = $choices[$previous]
+    } elseif ($wordToComplete -like '-*') {
+        $candidates = @('-h', '--help', '--zip', '--recursive', '--force-type', '--arch', '--list-properties', '--signature', '--ae-effects-only', '--strict', '--resource-id', '--timeout', '--max-properties', '--report-format-detection-confidence', '--watch', '--diff', '--format', '--stats', '--output-template', '--list', '--list-types', '--color-severity', '--no-color', '-v', '--verbose', '-q', '--quiet', '--version-format', '--min-version', '--check-compat', '--check-pipl-version', '--write-lockfile', '--check-lockfile', '--emit-cmake-version-file', '--base64-pipl', '--print-sdk-changelog')
+    } else {
+        $candidates = @('cache', 'dump', 'patch')
+        $candidates += Get-ChildItem -Path "$wordToComplete*" -Include '*.rsrc', '*.rcp', '*.aex', '*.dll', '*.dylib', '*.plugin' -Name -ErrorAction SilentlyContinue
+        $candidates += Get-ChildItem -Path "$wordToComplete*" -Directory -Name -ErrorAction SilentlyContinue
+    }
+
+    $candidates | Where-Object { $_ -like "$wordToComplete*" } | ForEach-Object {
+        [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterValue', $_)
+    }
+}
@@ -0,0 +1,31 @@
+#!/usr/bin/env python3
+"""Scan a directory of plugins in parallel and print each one's effect version.
+
+Usage: python3 examples/batch_scan.py /path/to/Plug-ins
+"""
+
+import os
+import sys
+from concurrent.futures import ThreadPoolExecutor
+
+sys.path.insert(0, os.path.join(os.path.dirname(os.path.abspath(__file__)), '..'))
+
+from ae_pipl_extractor import detect_file_type, expand_input_paths, parse_file_entries
+from pipl_types import PiplError
+
+def scan(path):
+    file_type = detect_file_type(path)
+    try:
+        return path, parse_file_entries(path, file_type) if file_type else []
+    except PiplError:
+        return path, []
+
+def main():
+    paths = expand_input_paths(sys.argv[1:])
+    with ThreadPoolExecutor() as executor:
+        for path, entries in executor.map(scan, paths):
+            versions = [str(entry.version_info) for entry in entries if entry.version_info]
+            print(f"{path}: {', '.join(versions) or 'no effect version'}")
+
+if __name__ == '__main__':
+    main()
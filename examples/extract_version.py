@@ -0,0 +1,24 @@
+#!/usr/bin/env python3
+"""Print the effect version of a plugin using the library API.
+
+Usage: python3 examples/extract_version.py Plugin.rsrc
+"""
+
+import os
+import sys
+
+sys.path.insert(0, os.path.join(os.path.dirname(os.path.abspath(__file__)), '..'))
+
+from ae_pipl_extractor import detect_file_type, parse_file_entries
+
+def main():
+    path = sys.argv[1]
+    entries = parse_file_entries(path, detect_file_type(path))
+    if not entries or not entries[0].version_info:
+        sys.exit(f"No effect version found in {path}")
+
+    version = entries[0].version_info
+    print(f"{version} ({version.encode_to_hex_string()})")
+
+if __name__ == '__main__':
+    main()
@@ -0,0 +1,33 @@
+#!/usr/bin/env python3
+"""Write a new effect version into the eVER property of a .rsrc file.
+
+The encoded version is always 4 bytes, so the file is patched in place
+without touching the resource map.
+
+Usage: python3 examples/inject_version.py Plugin.rsrc "1.2.0 Release (Build 5)"
+"""
+
+import os
+import sys
+
+sys.path.insert(0, os.path.join(os.path.dirname(os.path.abspath(__file__)), '..'))
+
+from pipl_builder import patch_8bim_version
+from pipl_types import VersionInfo, find_effect_version
+from resource_fork_parser import ResourceForkParser
+
+def main():
+    path, version_text = sys.argv[1], sys.argv[2]
+    new_version = VersionInfo.from_str(version_text)
+
+    with open(path, 'rb') as f:
+        data = f.read()
+    old_version = find_effect_version(ResourceForkParser.from_bytes(data, path).parse_pipl_properties())
+
+    with open(path, 'wb') as f:
+        f.write(patch_8bim_version(data, new_version))
+
+    print(f"{path}: {old_version} -> {new_version}")
+
+if __name__ == '__main__':
+    main()
@@ -0,0 +1,24 @@
+#!/usr/bin/env python3
+"""List every resource in a Mac resource fork, not just the PiPL.
+
+Usage: python3 examples/list_resources.py Plugin.rsrc
+"""
+
+import os
+import sys
+
+sys.path.insert(0, os.path.join(os.path.dirname(os.path.abspath(__file__)), '..'))
+
+from resource_fork_parser import ResourceForkParser
+
+def main():
+    parser = ResourceForkParser(sys.argv[1])
+
+    for summary in parser.summarize_resource_types():
+        print(f"'{summary.resource_type}': {summary.resource_count} resources, {summary.data_bytes} bytes")
+
+    for entry in parser.get_resource_entries():
+        print(f"  {entry.resource_type} {entry.resource_id:>6}  {entry.data_length:>8} bytes at {entry.data_offset:#x}")
+
+if __name__ == '__main__':
+    main()
@@ -0,0 +1,39 @@
+#!/usr/bin/env python3
+"""Print an annotated hex view of every PiPL property in a .rsrc file.
+
+Each property gets a header with its key and signature, its raw data as
+hex and ASCII columns numbered by file offset, and its decoded value when
+the key is a known one.
+
+Usage: python3 examples/pipl_dump.py Plugin.rsrc
+"""
+
+import os
+import sys
+
+sys.path.insert(0, os.path.join(os.path.dirname(os.path.abspath(__file__)), '..'))
+
+from pipl_types import DecodedKind, PIPL_PROPERTY_TYPES
+from resource_fork_parser import ResourceForkParser, hex_dump
+
+BOX_WIDTH = 78
+
+def main():
+    properties = ResourceForkParser(sys.argv[1]).parse_pipl_properties()
+
+    for prop in properties:
+        key = prop.kind_code()
+        title = (f" {key.decode('mac_roman')} ({PIPL_PROPERTY_TYPES.get(key, 'unknown')})  "
+                 f"signature {prop.signature.decode('mac_roman')}  {prop.length} bytes ")
+        print('┌' + '─' * (BOX_WIDTH - 2) + '┐')
+        print('│' + title.ljust(BOX_WIDTH - 2) + '│')
+        print('└' + '─' * (BOX_WIDTH - 2) + '┘')
+        print(hex_dump(bytes(prop.data), prop.data_offset or 0))
+
+        decoded = prop.decoded_value()
+        if decoded.kind is not DecodedKind.RAW:
+            print(f"  = {decoded.value} ({decoded.kind.value})")
+        print()
+
+if __name__ == '__main__':
+    main()
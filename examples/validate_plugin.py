@@ -0,0 +1,54 @@
+#!/usr/bin/env python3
+"""Check that a plugin's PiPL has everything After Effects needs to load it.
+
+Usage: python3 examples/validate_plugin.py Plugin.rsrc
+"""
+
+import os
+import sys
+
+sys.path.insert(0, os.path.join(os.path.dirname(os.path.abspath(__file__)), '..'))
+
+from ae_pipl_extractor import detect_file_type, parse_file_entries
+from pipl_types import (
+    AE_REQUIRED_PROPERTY_TYPES, PluginMetadata, is_ae_effect_plugin, is_valid_ae_plugin, normalize_property_type
+)
+
+def validate(properties):
+    """Return a list of problems with the PiPL, empty if it's valid."""
+    problems = []
+
+    if not is_valid_ae_plugin(properties):
+        present = {normalize_property_type(prop.property_type) for prop in properties}
+        missing = [key for key in AE_REQUIRED_PROPERTY_TYPES if key not in present]
+        problems.append(f"missing required properties: {', '.join(missing)}")
+
+    if not is_ae_effect_plugin(properties):
+        problems.append("Kind is not an After Effects effect (eFKT)")
+
+    metadata = PluginMetadata.from_properties(properties)
+    if not metadata.match_name:
+        problems.append("match name (eMNA) is empty")
+
+    return problems
+
+def main():
+    path = sys.argv[1]
+    entries = parse_file_entries(path, detect_file_type(path))
+    if not entries:
+        sys.exit(f"No PiPL found in {path}")
+
+    failed = False
+    for entry in entries:
+        label = f"PiPL {entry.resource_id}" if entry.resource_id is not None else "PiPL"
+        problems = validate(entry.properties)
+        for problem in problems:
+            print(f"{label}: {problem}")
+        if not problems:
+            print(f"{label}: OK ({entry.version_info})")
+        failed = failed or bool(problems)
+
+    sys.exit(1 if failed else 0)
+
+if __name__ == '__main__':
+    main()
@@ -0,0 +1,153 @@
+"""Parser for Mach-O binaries that embed resource fork data in a __rsrc section."""
+
+import struct
+from typing import Dict, List, Optional
+
+MH_MAGIC = 0xFEEDFACE
+MH_CIGAM = 0xCEFAEDFE
+MH_MAGIC_64 = 0xFEEDFACF
+MH_CIGAM_64 = 0xCFFAEDFE
+FAT_MAGIC = 0xCAFEBABE
+
+LC_SEGMENT = 0x1
+LC_SEGMENT_64 = 0x19
+
+# Architecture names for the CPU types found in AE plug-in binaries
+CPU_TYPE_NAMES = {
+    0x7: 'i386',
+    0x12: 'ppc',
+    0x01000007: 'x86_64',
+    0x0100000C: 'arm64'
+}
+
+def is_mach_o(data: bytes) -> bool:
+    """Check whether the data starts with a thin or fat Mach-O magic number."""
+    if len(data) < 4:
+        return False
+    magic = struct.unpack('>I', data[:4])[0]
+    return magic in (MH_MAGIC, MH_CIGAM, MH_MAGIC_64, MH_CIGAM_64, FAT_MAGIC)
+
+class MachOParser:
+    """Parse Mach-O binaries to locate resource data embedded in a __rsrc section."""
+
+    def __init__(self, file_path: str):
+        self.file_path = file_path
+        self.data = b''
+        self._load_file()
+
+    @classmethod
+    def from_bytes(cls, data: bytes, file_path: str = '<memory>') -> 'MachOParser':
+        """Create a parser over Mach-O data that is already in memory."""
+        parser = cls.__new__(cls)
+        parser.file_path = file_path
+        parser.data = data
+        return parser
+
+    def _load_file(self) -> None:
+        """Load the entire Mach-O binary into memory."""
+        try:
+            with open(self.file_path, 'rb') as f:
+                self.data = f.read()
+        except OSError as e:
+            raise OSError(f"Error loading Mach-O file: {e}") from e
+
+    def _thin_slices(self) -> List[Dict]:
+        """Return the thin Mach-O images contained in the file."""
+        if len(self.data) < 8:
+            return []
+
+        magic = struct.unpack('>I', self.data[:4])[0]
+        if magic != FAT_MAGIC:
+            endian = '<' if magic in (MH_CIGAM, MH_CIGAM_64) else '>'
+            cpu_type = struct.unpack(endian + 'i', self.data[4:8])[0]
+            return [{'cpu_type': cpu_type, 'offset': 0, 'size': len(self.data)}]
+
+        # Fat header and fat_arch entries are always big-endian
+        slices = []
+        num_arches = struct.unpack('>I', self.data[4:8])[0]
+        for i in range(num_arches):
+            arch_offset = 8 + (i * 20)  # Each fat_arch is 20 bytes
+            if arch_offset + 20 > len(self.data):
+                break
+            cpu_type, _, offset, size, _ = struct.unpack('>iiIII', self.data[arch_offset:arch_offset + 20])
+            if offset + size <= len(self.data):
+                slices.append({'cpu_type': cpu_type, 'offset': offset, 'size': size})
+
+        return slices
+
+    def _find_section(self, image: bytes, section_name: bytes) -> Optional[bytes]:
+        """Find a section by name in a thin Mach-O image and return its contents."""
+        if len(image) < 28:
+            return None
+
+        magic = struct.unpack('<I', image[:4])[0]
+        if magic in (MH_MAGIC, MH_MAGIC_64):
+            endian = '<'
+        elif magic in (MH_CIGAM, MH_CIGAM_64):
+            endian = '>'
+        else:
+            return None
+
+        is_64 = magic in (MH_MAGIC_64, MH_CIGAM_64)
+        num_commands = struct.unpack(endian + 'I', image[16:20])[0]
+        offset = 32 if is_64 else 28
+
+        for _ in range(num_commands):
+            if offset + 8 > len(image):
+                break
+            cmd, cmd_size = struct.unpack(endian + 'II', image[offset:offset + 8])
+
+            if cmd in (LC_SEGMENT, LC_SEGMENT_64):
+                if cmd == LC_SEGMENT_64:
+                    num_sections = struct.unpack(endian + 'I', image[offset + 64:offset + 68])[0]
+                    section_offset = offset + 72
+                    section_size = 80
+                else:
+                    num_sections = struct.unpack(endian + 'I', image[offset + 48:offset + 52])[0]
+                    section_offset = offset + 56
+                    section_size = 68
+
+                for _ in range(num_sections):
+                    if section_offset + section_size > len(image):
+                        break
+                    name = image[section_offset:section_offset + 16].rstrip(b'\x00')
+                    if name == section_name:
+                        if cmd == LC_SEGMENT_64:
+                            size = struct.unpack(endian + 'Q', image[section_offset + 40:section_offset + 48])[0]
+                            data_offset = struct.unpack(endian + 'I', image[section_offset + 48:section_offset + 52])[0]
+                        else:
+                            size = struct.unpack(endian + 'I', image[section_offset + 36:section_offset + 40])[0]
+                            data_offset = struct.unpack(endian + 'I', image[section_offset + 40:section_offset + 44])[0]
+                        if data_offset + size <= len(image):
+                            return image[data_offset:data_offset + size]
+                    section_offset += section_size
+
+            if cmd_size == 0:
+                break
+            offset += cmd_size
+
+        return None
+
+    def extract_rsrc_sections(self) -> List[Dict]:
+        """Return the __rsrc section of every slice that has one, labelled by architecture."""
+        sections = []
+
+        for image_slice in self._thin_slices():
+            image = self.data[image_slice['offset']:image_slice['offset'] + image_slice['size']]
+            rsrc = self._find_section(image, b'__rsrc')
+            if rsrc:
+                cpu_type = image_slice['cpu_type']
+                sections.append({
+                    'arch': CPU_TYPE_NAMES.get(cpu_type, f'cpu_{cpu_type:#x}'),
+                    'data': rsrc
+                })
+
+        return sections
+
+    def extract_rsrc_section(self, arch: Optional[str] = None) -> Optional[bytes]:
+        """Return the contents of the first __rsrc section, optionally for a specific architecture."""
+        for section in self.extract_rsrc_sections():
+            if arch is None or section['arch'] == arch:
+                return section['data']
+
+        return None
@@ -0,0 +1,143 @@
+"""Builder for constructing PiPL resource data programmatically."""
+
+import struct
+from typing import Dict, List, Tuple
+
+from pipl_types import (
+    PIPL_CURRENT_VERSION, PIPL_WINDOWS_HEADER_VERSION, MalformedChunkError, PiplError, PiplProperty, VersionInfo
+)
+from resource_fork_parser import RESOURCE_MAP_HEADER_SIZE
+
+class PiplBuilder:
+    """Build PiPL resource blobs in Mac (big-endian) or Windows (little-endian) layout."""
+
+    def __init__(self):
+        # Each entry is (key, data, is_u32). Numeric values are stored big-endian
+        # and byte-swapped when building the Windows layout.
+        self.properties: List[Tuple[bytes, bytes, bool]] = []
+
+    def _add(self, key: bytes, data: bytes, is_u32: bool = False) -> 'PiplBuilder':
+        if len(key) != 4:
+            raise ValueError(f"Property key must be 4 bytes, got {key!r}")
+        self.properties.append((key, data, is_u32))
+        return self
+
+    @staticmethod
+    def _pascal_string(text: str) -> bytes:
+        """Encode text as a Pascal string (length byte followed by the characters)."""
+        encoded = text.encode('mac_roman')
+        if len(encoded) > 255:
+            raise ValueError(f"String is too long for a Pascal string: {text!r}")
+        return bytes([len(encoded)]) + encoded
+
+    def add_version(self, info: VersionInfo) -> 'PiplBuilder':
+        """Add an AE_Effect_Version (eVER) property."""
+        return self._add(b'eVER', struct.pack('>I', info.to_encoded()), is_u32=True)
+
+    def add_name(self, name: str) -> 'PiplBuilder':
+        """Add a Name (name) property."""
+        return self._add(b'name', self._pascal_string(name))
+
+    def add_category(self, category: str) -> 'PiplBuilder':
+        """Add a Category (catg) property."""
+        return self._add(b'catg', self._pascal_string(category))
+
+    def add_raw(self, key: bytes, data: bytes) -> 'PiplBuilder':
+        """Add a property with arbitrary data, written as-is in both layouts."""
+        return self._add(key, bytes(data))
+
+    def build_mac(self) -> bytes:
+        """Build a big-endian PiPL with each property padded to a 4-byte boundary."""
+        data = struct.pack('>II', PIPL_CURRENT_VERSION, len(self.properties))
+
+        for key, value, _ in self.properties:
+            padding = (4 - len(value) % 4) % 4
+            data += b'8BIM' + key + struct.pack('>II', 0, len(value)) + value + b'\x00' * padding
+
+        return data
+
+    def build_windows(self) -> bytes:
+        """Build a little-endian PiPL as compiled into Windows resources, without padding."""
+        data = struct.pack('<HII', PIPL_WINDOWS_HEADER_VERSION, 0, len(self.properties))
+
+        for key, value, is_u32 in self.properties:
+            if is_u32:
+                value = struct.pack('<I', struct.unpack('>I', value)[0])
+            # Windows resources store the signature and key byte-reversed
+            data += b'MIB8' + key[::-1] + struct.pack('<II', 0, len(value)) + value
+
+        return data
+
+def write_8bim_pipl(properties: List[PiplProperty]) -> bytes:
+    """Serialize properties as a bare big-endian 8BIM chunk sequence, padded to 4 bytes.
+
+    Property data is written as-is, so properties read from Windows resources must
+    already be normalized to big-endian.
+    """
+    data = b''
+
+    for prop in properties:
+        value = bytes(prop.data)
+        padding = (4 - len(value) % 4) % 4
+        data += b'8BIM' + prop.kind_code() + struct.pack('>II', 0, len(value)) + value + b'\x00' * padding
+
+    return data
+
+def patch_8bim_version(data: bytes, new_version: VersionInfo) -> bytes:
+    """Return a copy of an 8BIM chunk sequence with the eVER value replaced in place."""
+    offset = data.find(b'8BIMeVER')
+    if offset == -1 or offset + 16 > len(data):
+        raise PiplError("No 8BIM eVER chunk found")
+
+    length = struct.unpack('>I', data[offset + 12:offset + 16])[0]
+    value_offset = offset + 16
+    if length == 8:
+        # Skip the 4-byte secondary header some files put before the version value
+        value_offset += 4
+    elif length != 4:
+        raise MalformedChunkError(f"eVER chunk has length {length}, expected 4 or 8")
+    if value_offset + 4 > len(data):
+        raise PiplError("eVER chunk runs past the end of the data")
+
+    patched = bytearray(data)
+    patched[value_offset:value_offset + 4] = struct.pack('>I', new_version.to_encoded())
+    return bytes(patched)
+
+def build_resource_fork(resources: List[Tuple[bytes, int, bytes]]) -> bytes:
+    """Build a Mac resource fork from (type, id, data) tuples."""
+    data_offset = 256
+
+    # Data section: each resource is a 4-byte length followed by its bytes
+    data_section = b''
+    resource_offsets = []
+    for _, _, resource_data in resources:
+        resource_offsets.append(len(data_section))
+        data_section += struct.pack('>I', len(resource_data)) + resource_data
+
+    # Group resources by type, preserving first-seen order
+    types: Dict[bytes, List[int]] = {}
+    for index, (resource_type, _, _) in enumerate(resources):
+        types.setdefault(resource_type, []).append(index)
+
+    type_list = struct.pack('>H', len(types) - 1)
+    reference_lists = b''
+    reference_list_base = 2 + 8 * len(types)
+    for resource_type, indexes in types.items():
+        type_list += resource_type + struct.pack('>HH', len(indexes) - 1,
+                                                 reference_list_base + len(reference_lists))
+        for index in indexes:
+            _, resource_id, _ = resources[index]
+            reference_lists += struct.pack('>hHI', resource_id, 0xFFFF,
+                                           resource_offsets[index] & 0x00FFFFFF)
+            reference_lists += b'\x00' * 4
+
+    # The type list directly follows the map header
+    type_list_offset = RESOURCE_MAP_HEADER_SIZE
+    name_list_offset = type_list_offset + len(type_list) + len(reference_lists)
+    map_offset = data_offset + len(data_section)
+    map_length = name_list_offset
+
+    header = struct.pack('>IIII', data_offset, map_offset, len(data_section), map_length)
+    resource_map = header + b'\x00' * 8 + struct.pack('>HH', type_list_offset, name_list_offset)
+
+    return header + b'\x00' * (data_offset - 16) + data_section + resource_map + type_list + reference_lists
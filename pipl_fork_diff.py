@@ -0,0 +1,71 @@
+"""Compare the resource maps of two resource fork files entry by entry."""
+
+from dataclasses import dataclass, field
+from typing import Dict, List, Tuple
+
+from resource_fork_parser import ResourceForkParser
+
+ResourceKey = Tuple[str, int]  # (resource type, resource ID)
+
+@dataclass
+class PiplForkDiff:
+    """Resources removed, added, unchanged and modified between two resource forks."""
+    removed: List[ResourceKey] = field(default_factory=list)
+    added: List[ResourceKey] = field(default_factory=list)
+    unchanged: List[ResourceKey] = field(default_factory=list)
+    modified: List[ResourceKey] = field(default_factory=list)
+    old_sizes: Dict[ResourceKey, int] = field(default_factory=dict)
+    new_sizes: Dict[ResourceKey, int] = field(default_factory=dict)
+
+    @classmethod
+    def compare(cls, old: bytes, new: bytes) -> 'PiplForkDiff':
+        """Compare two resource forks and classify every resource they list."""
+        old_resources = cls._read_resources(old)
+        new_resources = cls._read_resources(new)
+
+        diff = cls(
+            old_sizes={key: len(data) for key, data in old_resources.items()},
+            new_sizes={key: len(data) for key, data in new_resources.items()}
+        )
+
+        for key in sorted(old_resources.keys() | new_resources.keys()):
+            if key not in new_resources:
+                diff.removed.append(key)
+            elif key not in old_resources:
+                diff.added.append(key)
+            elif old_resources[key] == new_resources[key]:
+                diff.unchanged.append(key)
+            else:
+                diff.modified.append(key)
+
+        return diff
+
+    @staticmethod
+    def _read_resources(data: bytes) -> Dict[ResourceKey, bytes]:
+        """Read every resource in a fork keyed by (type, ID)."""
+        parser = ResourceForkParser.from_bytes(data)
+        return {
+            (entry.resource_type, entry.resource_id): bytes(entry.read_data(data))
+            for entry in parser.get_resource_entries()
+        }
+
+    def format_stat(self) -> str:
+        """Format the diff as a per-resource summary similar to `git diff --stat`."""
+        lines = []
+
+        for key in sorted(self.removed + self.added + self.modified):
+            resource_type, resource_id = key
+            label = f"{resource_type} {resource_id}"
+            if key in self.removed:
+                change = f"removed ({self.old_sizes[key]} bytes)"
+            elif key in self.added:
+                change = f"added ({self.new_sizes[key]} bytes)"
+            else:
+                change = f"modified ({self.old_sizes[key]} -> {self.new_sizes[key]} bytes)"
+            lines.append(f" {label:<16} | {change}")
+
+        changed = len(self.removed) + len(self.added) + len(self.modified)
+        lines.append(f" {changed} resources changed, {len(self.added)} added, "
+                     f"{len(self.removed)} removed, {len(self.unchanged)} unchanged")
+
+        return '\n'.join(lines)
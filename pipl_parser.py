@@ -0,0 +1,170 @@
+"""Parse raw PiPL resource data in either Mac or Windows byte order."""
+
+import base64
+import binascii
+import struct
+from enum import Enum
+from typing import Iterator, List, Optional, Tuple
+
+from pipl_builder import write_8bim_pipl
+from resource_fork_parser import ParseConfig
+from pipl_types import (
+    MAX_PIPL_PROPERTIES, PIPL_CURRENT_VERSION, PIPL_WINDOWS_HEADER_VERSION, MalformedChunkError, PiplError,
+    PiplProperty, TooManyPropertiesError, UnsupportedPiplVersionError, normalize_aex_property, normalize_property_type,
+    require_bytes
+)
+
+# Size of the header before the first property: version and count on Mac,
+# a 16-bit version followed by 32-bit reserved and count fields on Windows
+MAC_PIPL_HEADER_SIZE = 8
+WINDOWS_PIPL_HEADER_SIZE = 10
+
+class Alignment(Enum):
+    """Padding rule between consecutive PiPL properties."""
+    MAC = 'mac'  # Each property's data is zero-padded to a 4-byte boundary
+    WINDOWS = 'windows'  # Properties follow each other directly, so the length field is exact
+
+    def padding(self, length: int) -> int:
+        """Number of padding bytes after a property with `length` bytes of data."""
+        return (4 - length % 4) % 4 if self is Alignment.MAC else 0
+
+    @classmethod
+    def for_byte_order(cls, big_endian: bool) -> 'Alignment':
+        """Mac PiPLs are big-endian and padded; Windows resources are little-endian and packed."""
+        return cls.MAC if big_endian else cls.WINDOWS
+
+def pipl_data_is_big_endian(data: bytes) -> bool:
+    """Guess the byte order of PiPL data from the signature of its first property.
+
+    Mac PiPLs mark each property with '8BIM' and Windows resources with the
+    byte-reversed 'MIB8'. The signature is looked for at the start of the data
+    (a bare property list) and after the Mac and Windows PiPL headers.
+
+    Assumes the first property is well formed: data that starts mid-property
+    or has neither signature in those places is reported as big-endian, the
+    byte order Adobe defines PiPLs in.
+    """
+    for offset in (0, MAC_PIPL_HEADER_SIZE, WINDOWS_PIPL_HEADER_SIZE):
+        signature = bytes(data[offset:offset + 4])
+        if signature == b'8BIM':
+            return True
+        if signature == b'MIB8':
+            return False
+
+    return True
+
+def parse_pipl_data(data: bytes, max_properties: int = MAX_PIPL_PROPERTIES) -> List[PiplProperty]:
+    """Parse the properties of a PiPL resource, detecting its byte order."""
+    return list(PiplReader(data, max_properties=max_properties))
+
+def parse_pipl_from_base64(encoded: str) -> List[PiplProperty]:
+    """Decode base64 PiPL data, as stored in JSON or TOML configs, and parse its properties."""
+    try:
+        data = base64.b64decode(encoded, validate=True)
+    except binascii.Error as e:
+        raise PiplError(f"Invalid base64 PiPL data: {e}") from None
+    return parse_pipl_data(data)
+
+def pipl_to_base64(properties: List[PiplProperty]) -> str:
+    """Serialize properties as a big-endian PiPL and encode it as base64."""
+    data = struct.pack('>II', PIPL_CURRENT_VERSION, len(properties)) + write_8bim_pipl(properties)
+    return base64.b64encode(data).decode('ascii')
+
+def parse_pipl_data_with_offsets(data: bytes, config: Optional[ParseConfig] = None) -> List[Tuple[int, PiplProperty]]:
+    """Parse PiPL properties along with the offset of each property's signature in `data`."""
+    config = config or ParseConfig()
+    reader = PiplReader(data, max_properties=config.max_properties)
+    return [(reader.property_offset, prop) for prop in reader]
+
+class PiplReader:
+    """Iterate over the properties of PiPL data one at a time.
+
+    Unlike the scanning parsers, the reader walks the property list structurally
+    and raises instead of skipping over bytes it can't make sense of, so callers
+    that only need one property can stop without parsing the rest.
+    """
+
+    def __init__(self, data: bytes, big_endian: Optional[bool] = None, alignment: Optional[Alignment] = None,
+                 max_properties: int = MAX_PIPL_PROPERTIES):
+        self.data = data
+        self.big_endian = pipl_data_is_big_endian(data) if big_endian is None else big_endian
+        self.alignment = Alignment.for_byte_order(self.big_endian) if alignment is None else alignment
+        self.signature = b'8BIM' if self.big_endian else b'MIB8'
+        self.max_properties = max_properties
+        self.property_count = 0
+
+        # Skip the PiPL header, if any, by starting at the first property signature
+        start = bytes(data[:WINDOWS_PIPL_HEADER_SIZE + 4]).find(self.signature)
+        self.offset = start if start >= 0 else len(data)
+        # Offset of the signature of the property returned last
+        self.property_offset: Optional[int] = None
+
+        header = self._read_header(start)
+        if header is not None:
+            self.pipl_format_version, declared_count = header
+            # Other versions may lay out properties differently, so don't guess at them
+            expected = PIPL_CURRENT_VERSION if self.big_endian else PIPL_WINDOWS_HEADER_VERSION
+            if self.pipl_format_version != expected:
+                raise UnsupportedPiplVersionError(self.pipl_format_version, expected)
+            # Reject a corrupt declared count up front rather than reading until the data runs out
+            if declared_count > max_properties:
+                raise TooManyPropertiesError(declared_count, max_properties)
+        else:
+            self.pipl_format_version = None
+
+    def _read_header(self, start: int) -> Optional[Tuple[int, int]]:
+        """The (version, property count) from the PiPL header, or None for a bare property list."""
+        if self.big_endian and start == MAC_PIPL_HEADER_SIZE:
+            return struct.unpack('>II', self.data[:8])
+        if not self.big_endian and start == WINDOWS_PIPL_HEADER_SIZE:
+            version, _, count = struct.unpack('<HII', self.data[:10])
+            return version, count
+        return None
+
+    def __iter__(self) -> Iterator[PiplProperty]:
+        return self
+
+    def __next__(self) -> PiplProperty:
+        if self.offset >= len(self.data):
+            raise StopIteration
+        if self.property_count >= self.max_properties:
+            raise TooManyPropertiesError(self.property_count + 1, self.max_properties)
+
+        offset = self.offset
+        require_bytes(self.data, offset, 16)
+        if self.data[offset:offset + 4] != self.signature:
+            raise MalformedChunkError(f"Expected {self.signature!r} at offset {offset:#x}")
+
+        key = bytes(self.data[offset + 4:offset + 8])
+        length = struct.unpack('>I' if self.big_endian else '<I', self.data[offset + 12:offset + 16])[0]
+        data_start = offset + 16
+        require_bytes(self.data, data_start, length)
+        data_end = data_start + length
+        value = bytes(self.data[data_start:data_end])
+
+        if self.big_endian:
+            property_type = key.decode('mac_roman')
+        else:
+            property_type, value = normalize_aex_property(key, value)
+        self.offset = data_end + self.alignment.padding(length)
+
+        self.property_offset = offset
+        self.property_count += 1
+        return PiplProperty(property_type=property_type, data=value, length=len(value))
+
+def find_property_range(data: bytes, key: bytes) -> Optional[Tuple[int, int]]:
+    """Return the (start, end) byte range of the first property chunk with the given key, signature included."""
+    reader = PiplReader(data)
+    for prop in reader:
+        if normalize_property_type(prop.property_type) == key.decode('mac_roman'):
+            return reader.property_offset, reader.offset
+
+    return None
+
+def find_property(data: bytes, key: bytes) -> Optional[PiplProperty]:
+    """Return the first property with the given 4-character key, reading no further than needed."""
+    for prop in PiplReader(data):
+        if normalize_property_type(prop.property_type) == key.decode('mac_roman'):
+            return prop
+
+    return None
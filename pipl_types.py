@@ -1,9 +1,73 @@
 """PIPL property types and constants for After Effects plugins."""
 
-from dataclasses import dataclass
-from typing import Dict, Any, Optional
+import logging
+from dataclasses import dataclass, field, replace
+from typing import Callable, Dict, Any, List, Optional, Tuple
+import re
 import struct
-from enum import IntEnum
+import warnings
+from enum import Enum, IntEnum, IntFlag
+
+logger = logging.getLogger(__name__)
+
+class PiplError(Exception):
+    """Base class for errors raised while locating or decoding PiPL data."""
+
+class AmbiguousUniversalBinaryError(PiplError):
+    """Raised when the slices of a universal binary declare different effect versions."""
+
+class TruncatedDataError(PiplError):
+    """Raised when a declared offset and length run past the end of the data."""
+
+class UnexpectedEofError(PiplError, ValueError):
+    """Raised when fewer bytes remain than a field needs."""
+
+    def __init__(self, position: int, needed: int, available: int):
+        super().__init__(f"Need {needed} bytes at offset {position}, but only {max(available - position, 0)} remain")
+        self.position = position
+        self.needed = needed
+
+def require_bytes(data: bytes, position: int, needed: int) -> None:
+    """Raise UnexpectedEofError unless `needed` bytes are available at `position`."""
+    if position < 0 or position + needed > len(data):
+        raise UnexpectedEofError(position, needed, len(data))
+
+class MalformedChunkError(PiplError):
+    """Raised when an 8BIM property chunk declares a length its type can't have."""
+
+class VersionOutOfRangeError(PiplError, ValueError):
+    """Raised when a version field doesn't fit in its PF_VERSION bit field."""
+
+class UnsupportedPiplVersionError(PiplError):
+    """Raised when a PiPL header declares a format version this parser doesn't read."""
+
+    def __init__(self, found: int, expected: int):
+        super().__init__(f"Unsupported PiPL version {found} (expected {expected})")
+        self.found = found
+        self.expected = expected
+
+class TooManyPropertiesError(PiplError):
+    """Raised when a PiPL declares or contains more properties than the configured maximum."""
+
+    def __init__(self, count: int, max_count: int):
+        super().__init__(f"PiPL has {count} properties, more than the maximum of {max_count}")
+        self.count = count
+        self.max_count = max_count
+
+class FileTooSmallError(PiplError):
+    """Raised when a file is too short to be a resource fork or an 8BIM property stream."""
+
+    def __init__(self, size: int, minimum: int):
+        super().__init__(f"File is {size} bytes, smaller than the minimum of {minimum}")
+        self.size = size
+        self.minimum = minimum
+
+class InvalidStage(PiplError):
+    """Raised when a numeric stage value doesn't name a known stage."""
+
+    def __init__(self, value: int):
+        super().__init__(f"Invalid stage value: {value}")
+        self.value = value
 
 class Stage(IntEnum):
     """Version stage enumeration"""
@@ -12,9 +76,39 @@ class Stage(IntEnum):
     BETA = 2
     RELEASE = 3
 
-@dataclass
+    def __str__(self):
+        return self.name.capitalize()
+
+    @classmethod
+    def try_from(cls, value: int) -> 'Stage':
+        """Convert a numeric stage, raising InvalidStage instead of guessing for unknown values."""
+        try:
+            return cls(value)
+        except ValueError:
+            raise InvalidStage(value) from None
+
+    def to_json_value(self) -> str:
+        """Serialize the stage as a lowercase name like 'develop' or 'release'."""
+        return self.name.lower()
+
+    @classmethod
+    def from_json_value(cls, value: str) -> 'Stage':
+        """Deserialize a stage from its lowercase name."""
+        try:
+            return cls[value.upper()]
+        except KeyError:
+            raise ValueError(f"Unknown stage: {value!r}")
+
+# SemVer pre-release identifiers of the non-release stages
+SEMVER_PRERELEASE_STAGES = {
+    Stage.DEVELOP: 'dev',
+    Stage.ALPHA: 'alpha',
+    Stage.BETA: 'beta'
+}
+
+@dataclass(order=True)
 class VersionInfo:
-    """Version information container"""
+    """Version information container, ordered by version, stage and then build"""
     version: int
     subversion: int
     bugversion: int
@@ -22,19 +116,227 @@ class VersionInfo:
     build: int
 
     def __str__(self):
-        stage_names = {Stage.DEVELOP: "Develop", Stage.ALPHA: "Alpha",
-                      Stage.BETA: "Beta", Stage.RELEASE: "Release"}
-        return f"{self.version}.{self.subversion}.{self.bugversion} {stage_names[self.stage]} (Build {self.build})"
+        return f"{self.version}.{self.subversion}.{self.bugversion} {self.stage} (Build {self.build})"
 
-@dataclass
+    @classmethod
+    def zero(cls) -> 'VersionInfo':
+        """Return 0.0.0 Develop (Build 0), the value of an all-zero eVER."""
+        return cls(0, 0, 0, Stage.DEVELOP, 0)
+
+    @classmethod
+    def from_str(cls, text: str) -> 'VersionInfo':
+        """Parse the format produced by `__str__`, e.g. '1.5.0 Release (Build 3)'.
+
+        The minor and bugfix numbers, stage and build are optional and default to
+        the lowest value, so '1.5' is the first development build of 1.5.0.
+        """
+        match = re.fullmatch(r'\s*(\d+)(?:\.(\d+))?(?:\.(\d+))?(?:\s+([A-Za-z]+))?'
+                             r'(?:\s*\(\s*Build\s+(\d+)\s*\))?\s*', text, re.IGNORECASE)
+        if not match:
+            raise ValueError(f"Invalid version: {text!r}")
+
+        version, subversion, bugversion, stage, build = match.groups()
+        return cls(
+            version=int(version),
+            subversion=int(subversion or 0),
+            bugversion=int(bugversion or 0),
+            stage=Stage.from_json_value(stage) if stage else Stage.DEVELOP,
+            build=int(build or 0)
+        )
+
+    def is_compatible_with(self, host: 'VersionInfo') -> bool:
+        """Check whether a plugin with this version can load in the given host version.
+
+        Following the AE SDK rules, the major versions must match and the plugin's
+        version must not be newer than the host's. Stage and build are ignored.
+        """
+        return (self.version == host.version
+                and (self.subversion, self.bugversion) <= (host.subversion, host.bugversion))
+
+    def increment_build(self) -> 'VersionInfo':
+        """Return a copy with the build number bumped by one."""
+        if self.build >= PF_VERS_BUILD_BITS:
+            raise VersionOutOfRangeError(f"build {self.build + 1} is out of range (0-{PF_VERS_BUILD_BITS})")
+        return replace(self, build=self.build + 1)
+
+    def to_encoded(self) -> int:
+        """Encode into the 32-bit AE PF_VERSION format stored in eVER."""
+        return _encode_pf_version(self)
+
+    def encode_to_hex_string(self) -> str:
+        """Return the encoded eVER value as it's written in resource files, e.g. '0x05380001'."""
+        return f"0x{self.to_encoded():08X}"
+
+    def to_semver(self) -> str:
+        """Return a best-effort SemVer 2.0 string, e.g. '1.5.0-beta.3'.
+
+        Pre-release stages become a pre-release identifier followed by the build
+        number. SemVer orders pre-release versions before the release, so release
+        builds carry the build number as build metadata ('1.5.0+3'), or nothing
+        for build 0.
+        """
+        core = f"{self.version}.{self.subversion}.{self.bugversion}"
+        if self.stage != Stage.RELEASE:
+            return f"{core}-{SEMVER_PRERELEASE_STAGES[self.stage]}.{self.build}"
+        return f"{core}+{self.build}" if self.build else core
+
+    def __int__(self) -> int:
+        return self.to_encoded()
+
+    def to_dict(self) -> Dict[str, Any]:
+        """Convert to a plain dict suitable for JSON or other serializers."""
+        return {
+            'version': self.version,
+            'subversion': self.subversion,
+            'bugversion': self.bugversion,
+            'stage': self.stage.to_json_value(),
+            'build': self.build
+        }
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> 'VersionInfo':
+        """Build a VersionInfo from the dict produced by `to_dict`."""
+        return cls(
+            version=int(data['version']),
+            subversion=int(data['subversion']),
+            bugversion=int(data['bugversion']),
+            stage=Stage.from_json_value(data['stage']),
+            build=int(data['build'])
+        )
+
+@dataclass(eq=False)
 class PiplProperty:
     """Represents a single PIPL property."""
     property_type: str  # 4-character code like 'kind', 'name', etc.
     data: bytes
     length: int
+    data_offset: Optional[int] = None  # Offset of data in the input file, when known
+    signature: bytes = b'8BIM'  # Vendor signature, in Mac byte order like the key
 
     def __str__(self) -> str:
-        return f"Property(type='{self.property_type}', length={self.length})"
+        """Format as 'KEY: value' using the decoded value, e.g. 'eVER: 1.5.0 Release (Build 42)'."""
+        return f"{self.kind_code().decode('mac_roman')}: {format_decoded_value(self.decoded_value())}"
+
+    def __eq__(self, other: object) -> bool:
+        # Properties are equal when they have the same key and data, regardless of
+        # whether the key was read reversed from a Windows resource
+        if not isinstance(other, PiplProperty):
+            return NotImplemented
+        return (normalize_property_type(self.property_type) == normalize_property_type(other.property_type)
+                and bytes(self.data) == bytes(other.data))
+
+    def __hash__(self) -> int:
+        return hash((normalize_property_type(self.property_type), bytes(self.data)))
+
+    def kind_code(self) -> bytes:
+        """Return the 4-byte OSType key in its canonical (Mac) byte order."""
+        return normalize_property_type(self.property_type).encode('mac_roman')[:4].ljust(4, b' ')
+
+    def key_as_str(self) -> Optional[str]:
+        """Return the SDK name of a well-known key, e.g. 'AE_Effect_Version' for eVER, or None."""
+        return PIPL_PROPERTY_TYPES.get(self.kind_code())
+
+    def decoded_value(self) -> 'DecodedValue':
+        """Decode the data according to the key: see decode_property_value()."""
+        return decode_property_value(self)
+
+    def is_required_for_ae(self) -> bool:
+        """Check whether the AE SDK requires this property in every effect PiPL."""
+        return normalize_property_type(self.property_type) in AE_REQUIRED_PROPERTY_TYPES
+
+# Mapping for reversed property types (from RCP/AEX)
+REVERSED_PROPERTY_TYPES = {
+    'dnik': 'kind',
+    'eman': 'name',
+    'gtac': 'catg',
+    '4668': '8664',
+    'ANMe': 'eMNA',
+    'RVPe': 'ePVR',
+    'RVSe': 'eSVR',
+    'REVe': 'eVER',
+    'FNIe': 'eINF',
+    'OLGe': 'eGLO',
+    '2LGe': 'eGL2',
+    'LFea': 'aeFL',
+    ' glF': 'Flg ',
+    'doms': 'smod'
+}
+
+def normalize_property_type(prop_type: str) -> str:
+    """Normalize property types from different sources (direct, reversed, Windows)."""
+    return REVERSED_PROPERTY_TYPES.get(prop_type, prop_type)
+
+def normalize_aex_property(prop_type_bytes: bytes, prop_data: bytes) -> Tuple[str, bytes]:
+    """Normalize a Windows property type (reversed 4CC) and convert little-endian values to big-endian bytes.
+
+    This ensures downstream decoders (expecting big-endian like .rcp/.rsrc) behave consistently.
+    """
+    # Reverse 4CC like b'RVPe' -> 'ePVR'
+    try:
+        corrected_type = prop_type_bytes[::-1].decode('ascii', errors='ignore')
+    except Exception:
+        corrected_type = prop_type_bytes[::-1].hex()
+
+    # For known numeric properties, convert little-endian to big-endian byte order
+    if corrected_type in ('ePVR', 'eSVR'):
+        # Two 16-bit values in little-endian → repack as big-endian
+        if len(prop_data) >= 4:
+            major_le, minor_le = struct.unpack('<HH', prop_data[:4])
+            prop_data = struct.pack('>HH', major_le, minor_le)
+    elif corrected_type == 'smod':
+        # Single 16-bit value little-endian → big-endian
+        if len(prop_data) >= 2:
+            value_le = struct.unpack('<H', prop_data[:2])[0]
+            prop_data = struct.pack('>H', value_le)
+    elif corrected_type in ('eVER', 'eINF', 'eGLO', 'eGL2', 'aeFL', 'Flg '):
+        # Single 32-bit value little-endian → big-endian
+        if len(prop_data) >= 4:
+            value_le = struct.unpack('<I', prop_data[:4])[0]
+            prop_data = struct.pack('>I', value_le)
+    # Other types (strings/entry points) are left as-is
+
+    return corrected_type, prop_data
+
+# Properties every After Effects effect PiPL must declare
+AE_REQUIRED_PROPERTY_TYPES = ('kind', 'name', 'catg', 'eVER', 'eMNA')
+
+def is_valid_ae_plugin(properties: List[PiplProperty]) -> bool:
+    """Check that all properties required by After Effects are present."""
+    present = {normalize_property_type(prop.property_type) for prop in properties
+               if prop.is_required_for_ae()}
+    return present == set(AE_REQUIRED_PROPERTY_TYPES)
+
+def plugin_kind_code(properties: List[PiplProperty]) -> Optional[bytes]:
+    """Return the 4-byte code of the Kind (kind) property, or None if there isn't one."""
+    for prop in properties:
+        if normalize_property_type(prop.property_type) == 'kind' and len(prop.data) >= 4:
+            return bytes(prop.data[:4])
+    return None
+
+def is_ae_effect_plugin(properties: List[PiplProperty]) -> bool:
+    """Check whether the PiPL describes an After Effects effect (kind 'eFKT')."""
+    return plugin_kind_code(properties) == b'eFKT'
+
+def is_aegp_plugin(properties: List[PiplProperty]) -> bool:
+    """Check whether the PiPL describes an After Effects general plugin (kind 'AEgx')."""
+    return plugin_kind_code(properties) == b'AEgx'
+
+def is_photoshop_filter_plugin(properties: List[PiplProperty]) -> bool:
+    """Check whether the PiPL describes a Photoshop filter (kind '8BFM'), which AE can also host."""
+    return plugin_kind_code(properties) == b'8BFM'
+
+# PiPL SDK constants
+PIPL_TYPE_CODE = 0x5069504C  # 'PiPL' resource type
+PIPL_PROPERTY_SIGNATURE = 0x3842494D  # '8BIM' vendor signature of each property
+PIPL_CURRENT_VERSION = 0  # kCurrentPiPLVersion
+PIPL_WINDOWS_HEADER_VERSION = 1  # 16-bit version word that starts PiPLs compiled into Windows resources
+PIPL_DEFAULT_RESOURCE_ID = 16000  # Resource ID used by the SDK examples
+PIPL_MATCH_NAME_MAX_LEN = 32  # Match name buffer size, so at most 31 characters
+MAX_PIPL_PROPERTIES = 256  # Sanity cap on the property count, well above any real plug-in
+
+def os_type_to_str(code: int) -> str:
+    """Convert a 32-bit OSType like PIPL_TYPE_CODE to its 4-character string."""
+    return code.to_bytes(4, 'big').decode('mac_roman')
 
 # PIPL property type constants
 PIPL_PROPERTY_TYPES = {
@@ -51,18 +353,63 @@ PIPL_PROPERTY_TYPES = {
     b'eGLO': 'AE_Effect_Global_OutFlags',
     b'eGL2': 'AE_Effect_Global_OutFlags_2',
     b'eMNA': 'AE_Effect_Match_Name',
-    b'aeFL': 'AE_Reserved_Info'
+    b'aeFL': 'AE_Reserved_Info',
+    b'Flg ': 'AE_Effect_Flags',  # Non-standard, emitted by some third-party toolsets
+    b'smod': 'Supported_Modes'
 }
 
+# Summary of PiPL-related changes across After Effects SDK releases
+AE_SDK_CHANGELOG = """\
+After Effects SDK changes affecting PiPL resources
+==================================================
+
+Early SDKs
+  - Core effect properties: Kind (kind), Name (name), Category (catg),
+    AE_PiPL_Version (ePVR), AE_Effect_Spec_Version (eSVR),
+    AE_Effect_Version (eVER), AE_Effect_Info_Flags (eINF),
+    AE_Effect_Global_OutFlags (eGLO), AE_Effect_Match_Name (eMNA),
+    AE_Reserved_Info (aeFL).
+
+AE 5.0
+  - PF_OutFlag_DEEP_COLOR_AWARE for 16 bpc rendering.
+
+AE 6.x
+  - AE_Effect_Global_OutFlags_2 (eGL2) carries PF_OutFlag2_* capabilities.
+
+AE CS3 (8.0)
+  - SmartFX: PF_OutFlag2_SUPPORTS_SMART_RENDER and
+    PF_OutFlag2_FLOAT_COLOR_AWARE for 32 bpc float rendering.
+
+AE CS5 (10.0)
+  - 64-bit only hosts. Entry points are declared with CodeWin64X86 (8664)
+    and CodeMacIntel64 (mi64).
+
+AE CC 2015 (13.5)
+  - PF_OutFlag2_AE13_5_THREADSAFE for the multi-threaded render pipeline.
+
+AE CC 2019 (16.0)
+  - PF_OutFlag2_SUPPORTS_GPU_RENDER_F32 for GPU rendering.
+
+AE 2022 (22.0)
+  - Multi-Frame Rendering: PF_OutFlag2_SUPPORTS_THREADED_RENDERING and
+    PF_OutFlag2_MUTABLE_RENDER_SEQUENCE_DATA_SLOWER.
+  - Apple silicon builds declare their entry point with CodeMacARM64 (ma64).
+"""
+
 # Plugin kind constants
 PLUGIN_KINDS = {
     b'eFKT': 'AEEffect',
+    b'AEgx': 'AEGP',
     b'SPEA': 'AdobeSuitePea',
     b'ARPI': 'AdobeIllustrator',
     b'8BFM': 'FilterModule',
     b'8BIF': 'FormatModule'
 }
 
+# PF_OutFlag bits read by PluginMetadata
+OUT_FLAG_I_DO_DIALOG = 0x00000020
+OUT_FLAG_DEEP_COLOR_AWARE = 0x02000000
+
 # Standard After Effects flags
 AE_OUT_FLAGS = {
     0x00000001: 'PF_OutFlag_KEEP_RESOURCE_OPEN',
@@ -96,33 +443,63 @@ AE_OUT_FLAGS = {
     0x80000000: 'PF_OutFlag_AUDIO_EFFECT_ONLY'
 }
 
-AE_OUT_FLAGS_2 = {
-    0x00000001: 'PF_OutFlag2_SUPPORTS_QUERY_DYNAMIC_FLAGS',
-    0x00000002: 'PF_OutFlag2_I_USE_3D_CAMERA',
-    0x00000004: 'PF_OutFlag2_I_USE_3D_LIGHTS',
-    0x00000008: 'PF_OutFlag2_PARAM_GROUP_START_COLLAPSED_FLAG',
-    0x00000010: 'PF_OutFlag2_I_AM_THREADSAFE',
-    0x00000020: 'PF_OutFlag2_CAN_COMBINE_WITH_DESTINATION',
-    0x00000040: 'PF_OutFlag2_DOESNT_NEED_EMPTY_PIXELS',
-    0x00000080: 'PF_OutFlag2_REVEALS_ZERO_ALPHA',
-    0x00000100: 'PF_OutFlag2_PRESERVES_FULLY_OPAQUE_PIXELS',
-    0x00000400: 'PF_OutFlag2_SUPPORTS_SMART_RENDER',
-    0x00001000: 'PF_OutFlag2_FLOAT_COLOR_AWARE',
-    0x00002000: 'PF_OutFlag2_I_USE_COLORSPACE_ENUMERATION',
-    0x00004000: 'PF_OutFlag2_I_AM_DEPRECATED',
-    0x00008000: 'PF_OutFlag2_PPRO_DO_NOT_CLONE_SEQUENCE_DATA_FOR_RENDER',
-    0x00020000: 'PF_OutFlag2_AUTOMATIC_WIDE_TIME_INPUT',
-    0x00040000: 'PF_OutFlag2_I_USE_TIMECODE',
-    0x00080000: 'PF_OutFlag2_DEPENDS_ON_UNREFERENCED_MASKS',
-    0x00100000: 'PF_OutFlag2_OUTPUT_IS_WATERMARKED',
-    0x00200000: 'PF_OutFlag2_I_MIX_GUID_DEPENDENCIES',
-    0x00400000: 'PF_OutFlag2_AE13_5_THREADSAFE',
-    0x00800000: 'PF_OutFlag2_SUPPORTS_GET_FLATTENED_SEQUENCE_DATA',
-    0x01000000: 'PF_OutFlag2_CUSTOM_UI_ASYNC_MANAGER',
-    0x02000000: 'PF_OutFlag2_SUPPORTS_GPU_RENDER_F32',
-    0x08000000: 'PF_OutFlag2_SUPPORTS_THREADED_RENDERING',
-    0x10000000: 'PF_OutFlag2_MUTABLE_RENDER_SEQUENCE_DATA_SLOWER'
-}
+class OutFlags2(IntFlag):
+    """PF_OutFlag2 bits declared in the eGL2 (AE_Effect_Global_OutFlags_2) property."""
+    SUPPORTS_QUERY_DYNAMIC_FLAGS = 0x00000001
+    I_USE_3D_CAMERA = 0x00000002
+    I_USE_3D_LIGHTS = 0x00000004
+    PARAM_GROUP_START_COLLAPSED_FLAG = 0x00000008
+    I_AM_THREADSAFE = 0x00000010
+    CAN_COMBINE_WITH_DESTINATION = 0x00000020
+    DOESNT_NEED_EMPTY_PIXELS = 0x00000040
+    REVEALS_ZERO_ALPHA = 0x00000080
+    PRESERVES_FULLY_OPAQUE_PIXELS = 0x00000100
+    SUPPORTS_SMART_RENDER = 0x00000400
+    FLOAT_COLOR_AWARE = 0x00001000
+    I_USE_COLORSPACE_ENUMERATION = 0x00002000
+    I_AM_DEPRECATED = 0x00004000
+    PPRO_DO_NOT_CLONE_SEQUENCE_DATA_FOR_RENDER = 0x00008000
+    AUTOMATIC_WIDE_TIME_INPUT = 0x00020000
+    I_USE_TIMECODE = 0x00040000
+    DEPENDS_ON_UNREFERENCED_MASKS = 0x00080000
+    OUTPUT_IS_WATERMARKED = 0x00100000
+    I_MIX_GUID_DEPENDENCIES = 0x00200000
+    AE13_5_THREADSAFE = 0x00400000
+    SUPPORTS_GET_FLATTENED_SEQUENCE_DATA = 0x00800000
+    CUSTOM_UI_ASYNC_MANAGER = 0x01000000
+    SUPPORTS_GPU_RENDER_F32 = 0x02000000
+    SUPPORTS_THREADED_RENDERING = 0x08000000
+    MUTABLE_RENDER_SEQUENCE_DATA_SLOWER = 0x10000000
+
+    def flag_names(self) -> List[str]:
+        """Names of the set flag bits, in bit order."""
+        return [flag.name for flag in OutFlags2 if flag in self]
+
+# PF_OutFlag2 names by bit, for decode_flags
+AE_OUT_FLAGS_2 = {flag.value: f'PF_OutFlag2_{flag.name}' for flag in OutFlags2}
+
+def decode_outflags2(data: bytes) -> OutFlags2:
+    """Decode the 4-byte big-endian eGL2 bitmask, keeping any bits the table doesn't name."""
+    if len(data) < 4:
+        raise ValueError(f"eGL2 property needs 4 bytes, got {len(data)}")
+    return OutFlags2(struct.unpack('>I', data[:4])[0])
+
+class SupportedModes(IntFlag):
+    """Pixel modes declared in the 16-bit smod property."""
+    ARGB8 = 0x0001
+    ARGB16 = 0x0002
+    ARGB32F = 0x0004
+    BGRA8 = 0x0008
+
+    def mode_names(self) -> List[str]:
+        """Names of the set mode bits, in bit order."""
+        return [mode.name for mode in SupportedModes if mode in self]
+
+def decode_supported_modes(data: bytes) -> SupportedModes:
+    """Decode the 2-byte big-endian smod bitmask."""
+    if len(data) < 2:
+        raise ValueError(f"smod property needs 2 bytes, got {len(data)}")
+    return SupportedModes(struct.unpack('>H', data[:2])[0])
 
 def decode_flags(flags_value: int, flags_dict: Dict[int, str]) -> str:
     """Convert flags integer to readable flag names."""
@@ -164,27 +541,53 @@ def decode_string(data: bytes) -> str:
     # Try as plain string
     return data.decode('utf-8', errors='ignore')
 
+def decode_match_name(data: bytes) -> str:
+    """Decode the eMNA match name; see match_name_problems() for validating it."""
+    return decode_string(data)
+
+def match_name_problems(data: bytes) -> List[str]:
+    """Describe why the eMNA data isn't a valid After Effects match name, if it isn't.
+
+    After Effects identifies effects across projects by this ASCII string, which
+    may be at most PIPL_MATCH_NAME_MAX_LEN - 1 characters.
+    """
+    match_name = decode_string(data)
+    raw = bytes(data)
+    problems = []
+
+    if not raw.isascii():
+        problems.append(f"Match name {match_name!r} contains non-ASCII characters")
+    if len(match_name) > PIPL_MATCH_NAME_MAX_LEN - 1:
+        problems.append(f"Match name {match_name!r} is {len(match_name)} characters, "
+                        f"After Effects allows at most {PIPL_MATCH_NAME_MAX_LEN - 1}")
+    # Stored either as a Pascal string or as a null-terminated C string
+    is_pascal = len(raw) > 0 and 0 < raw[0] < len(raw)
+    if not is_pascal and b'\x00' not in raw:
+        problems.append(f"Match name {match_name!r} is not null-terminated")
+
+    return problems
+
 def decode_entry_point(data: bytes) -> str:
     """Decode entry point string from code property."""
     return decode_string(data)
 
+# PF_VERS constants from After Effects SDK
+PF_VERS_BUILD_BITS = 0x1ff
+PF_VERS_BUILD_SHIFT = 0
+PF_VERS_STAGE_BITS = 0x3
+PF_VERS_STAGE_SHIFT = 9
+PF_VERS_BUGFIX_BITS = 0xf
+PF_VERS_BUGFIX_SHIFT = 11
+PF_VERS_SUBVERS_BITS = 0xf
+PF_VERS_SUBVERS_SHIFT = 15
+PF_VERS_VERS_BITS = 0x7
+PF_VERS_VERS_SHIFT = 19
+PF_VERS_VERS_HIGH_BITS = 0xf
+PF_VERS_VERS_HIGH_SHIFT = 26
+PF_VERS_VERS_LOW_SHIFT = 3
+
 def extract_pf_version(encoded: int) -> VersionInfo:
     """Extract version information from encoded version value using AE format."""
-    # PF_VERS constants from After Effects SDK
-    PF_VERS_BUILD_BITS = 0x1ff
-    PF_VERS_BUILD_SHIFT = 0
-    PF_VERS_STAGE_BITS = 0x3
-    PF_VERS_STAGE_SHIFT = 9
-    PF_VERS_BUGFIX_BITS = 0xf
-    PF_VERS_BUGFIX_SHIFT = 11
-    PF_VERS_SUBVERS_BITS = 0xf
-    PF_VERS_SUBVERS_SHIFT = 15
-    PF_VERS_VERS_BITS = 0x7
-    PF_VERS_VERS_SHIFT = 19
-    PF_VERS_VERS_HIGH_BITS = 0xf
-    PF_VERS_VERS_HIGH_SHIFT = 26
-    PF_VERS_VERS_LOW_SHIFT = 3
-
     build = (encoded >> PF_VERS_BUILD_SHIFT) & PF_VERS_BUILD_BITS
     stage_num = (encoded >> PF_VERS_STAGE_SHIFT) & PF_VERS_STAGE_BITS
     bugversion = (encoded >> PF_VERS_BUGFIX_SHIFT) & PF_VERS_BUGFIX_BITS
@@ -194,13 +597,294 @@ def extract_pf_version(encoded: int) -> VersionInfo:
     version_high = (encoded >> PF_VERS_VERS_HIGH_SHIFT) & PF_VERS_VERS_HIGH_BITS
     version = (version_high << PF_VERS_VERS_LOW_SHIFT) | version_low
 
-    stage = Stage(stage_num) if stage_num in [0, 1, 2, 3] else Stage.DEVELOP
+    # The stage field is two bits wide, so every value names a stage
+    stage = Stage.try_from(stage_num)
 
     return VersionInfo(version, subversion, bugversion, stage, build)
 
+def encode_pf_version(info: VersionInfo) -> int:
+    """Encode version information into the AE PF_VERSION format.
+
+    Deprecated: use `VersionInfo.to_encoded()` or `int(info)` instead.
+    """
+    warnings.warn("encode_pf_version is deprecated, use VersionInfo.to_encoded", DeprecationWarning, stacklevel=2)
+    return _encode_pf_version(info)
+
+def _encode_pf_version(info: VersionInfo) -> int:
+    """Encode version information into the AE PF_VERSION format (inverse of extract_pf_version)."""
+    max_version = (PF_VERS_VERS_HIGH_BITS << PF_VERS_VERS_LOW_SHIFT) | PF_VERS_VERS_BITS
+    fields = [
+        ('version', info.version, max_version),
+        ('subversion', info.subversion, PF_VERS_SUBVERS_BITS),
+        ('bugversion', info.bugversion, PF_VERS_BUGFIX_BITS),
+        ('stage', int(info.stage), PF_VERS_STAGE_BITS),
+        ('build', info.build, PF_VERS_BUILD_BITS)
+    ]
+    for name, value, maximum in fields:
+        if not 0 <= value <= maximum:
+            raise ValueError(f"{name} {value} is out of range (0-{maximum})")
+
+    # The major version is split into low bits and high bits stored separately
+    version_low = info.version & PF_VERS_VERS_BITS
+    version_high = (info.version >> PF_VERS_VERS_LOW_SHIFT) & PF_VERS_VERS_HIGH_BITS
+
+    return ((version_high << PF_VERS_VERS_HIGH_SHIFT) |
+            (version_low << PF_VERS_VERS_SHIFT) |
+            (info.subversion << PF_VERS_SUBVERS_SHIFT) |
+            (info.bugversion << PF_VERS_BUGFIX_SHIFT) |
+            (int(info.stage) << PF_VERS_STAGE_SHIFT) |
+            (info.build << PF_VERS_BUILD_SHIFT))
+
 def decode_effect_version(data: bytes) -> Optional[VersionInfo]:
     """Decode effect version from property data."""
     if len(data) >= 4:
         encoded_version = struct.unpack('>I', data[:4])[0]
         return extract_pf_version(encoded_version)
-    return None
\ No newline at end of file
+    return None
+
+def find_effect_version(properties: List[PiplProperty]) -> Optional[VersionInfo]:
+    """Decode the first eVER property in a list of properties."""
+    return next((decode_effect_version(prop.data) for prop in properties
+                 if normalize_property_type(prop.property_type) == 'eVER'), None)
+
+class DecodedKind(Enum):
+    """How a property's data was decoded by PiplProperty.decoded_value()."""
+    VERSION = 'version'  # VersionInfo
+    PLUGIN_KIND = 'plugin_kind'  # PLUGIN_KINDS name, or the raw code for unknown kinds
+    SUPPORTED_MODES = 'supported_modes'  # SupportedModes
+    PASCAL_STRING = 'pascal_string'  # str
+    C_STRING = 'c_string'  # str
+    U32 = 'u32'  # int
+    RAW = 'raw'  # bytes
+
+@dataclass(frozen=True)
+class DecodedValue:
+    """The value of a PiPL property decoded according to its key."""
+    kind: DecodedKind
+    value: Any
+
+# Keys of the properties decoded by PiplProperty.decoded_value(), other than eVER, kind and smod
+PASCAL_STRING_PROPERTY_TYPES = frozenset({'name', 'catg', 'eMNA'})
+C_STRING_PROPERTY_TYPES = frozenset({'8664', 'mi64', 'ma64'})
+U32_PROPERTY_TYPES = frozenset({'eINF', 'eGLO', 'eGL2', 'aeFL', 'Flg '})
+
+def format_decoded_value(decoded: DecodedValue) -> str:
+    """Format a decoded value for display: numbers and raw 4-byte values as hex, other raw data as hex bytes."""
+    if decoded.kind is DecodedKind.U32:
+        return f"0x{decoded.value:08X}"
+    if decoded.kind is DecodedKind.SUPPORTED_MODES:
+        return ', '.join(decoded.value.mode_names()) or 'none'
+    if decoded.kind is DecodedKind.RAW:
+        return f"0x{decoded.value.hex().upper()}" if len(decoded.value) == 4 else decoded.value.hex(' ')
+    return str(decoded.value)
+
+def decode_property_value(prop: PiplProperty) -> DecodedValue:
+    """Decode a property's data according to its key, falling back to the raw bytes."""
+    key = normalize_property_type(prop.property_type)
+    data = bytes(prop.data)
+
+    if key == 'eVER' and len(data) >= 4:
+        return DecodedValue(DecodedKind.VERSION, decode_effect_version(data))
+    if key == 'kind' and len(data) >= 4:
+        code = data[:4]
+        return DecodedValue(DecodedKind.PLUGIN_KIND, PLUGIN_KINDS.get(code, code.decode('mac_roman')))
+    if key == 'smod' and len(data) >= 2:
+        return DecodedValue(DecodedKind.SUPPORTED_MODES, decode_supported_modes(data))
+    if key in PASCAL_STRING_PROPERTY_TYPES:
+        return DecodedValue(DecodedKind.PASCAL_STRING, decode_string(data))
+    if key in C_STRING_PROPERTY_TYPES:
+        return DecodedValue(DecodedKind.C_STRING, decode_entry_point(data))
+    if key in U32_PROPERTY_TYPES and len(data) >= 4:
+        return DecodedValue(DecodedKind.U32, struct.unpack('>I', data[:4])[0])
+    return DecodedValue(DecodedKind.RAW, data)
+
+# Development stage bytes of a classic Mac OS NumVersion
+VERS_STAGES = {
+    0x20: Stage.DEVELOP,
+    0x40: Stage.ALPHA,
+    0x60: Stage.BETA,
+    0x80: Stage.RELEASE
+}
+
+def _decode_bcd(value: int) -> int:
+    high, low = value >> 4, value & 0xf
+    if high > 9 or low > 9:
+        raise ValueError(f"Invalid BCD byte: {value:#04x}")
+    return high * 10 + low
+
+def parse_vers_resource(data: bytes) -> VersionInfo:
+    """Decode the NumVersion at the start of a classic Mac OS 'vers' resource."""
+    if len(data) < 4:
+        raise ValueError(f"'vers' resource is too short: {len(data)} bytes")
+
+    major, minor_and_bug, stage_byte, non_release_revision = data[:4]
+    if stage_byte not in VERS_STAGES:
+        raise ValueError(f"Unknown 'vers' stage: {stage_byte:#04x}")
+
+    minor_and_bug = _decode_bcd(minor_and_bug)
+    return VersionInfo(
+        version=_decode_bcd(major),
+        subversion=minor_and_bug // 10,
+        bugversion=minor_and_bug % 10,
+        stage=VERS_STAGES[stage_byte],
+        build=non_release_revision
+    )
+
+class VersionSource(Enum):
+    """Which resource an entry's version was read from."""
+    PIPL = 'pipl'
+    VERS = 'vers'
+
+@dataclass
+class PiplEntry:
+    """One PiPL resource found in a file, with its decoded effect version."""
+    resource_id: Optional[int]  # None when the PiPL wasn't located through a resource map
+    format: str  # Where the PiPL was read from, e.g. 'resource_fork', '8bim' or 'aex'
+    version_info: Optional[VersionInfo]
+    properties: List[PiplProperty]
+    version_source: VersionSource = VersionSource.PIPL
+
+    @classmethod
+    def from_properties(cls, properties: List[PiplProperty], format: str,
+                        resource_id: Optional[int] = None) -> 'PiplEntry':
+        """Build an entry from parsed properties, decoding the eVER property if present."""
+        version_info = find_effect_version(properties)
+        return cls(resource_id=resource_id, format=format, version_info=version_info, properties=properties)
+
+    def effect_version_or_default(self) -> VersionInfo:
+        """Return the effect version, or VersionInfo.zero() if the PiPL has no eVER."""
+        return self.effect_version_or_else(VersionInfo.zero)
+
+    def effect_version_or_else(self, default: Callable[[], VersionInfo]) -> VersionInfo:
+        """Return the effect version, or the result of default() if the PiPL has no eVER."""
+        return self.version_info if self.version_info is not None else default()
+
+    def summary(self) -> 'PiplSummary':
+        """Return a compact snapshot of the entry's key metadata."""
+        match_name = None
+        for prop in self.properties:
+            normalized_type = normalize_property_type(prop.property_type)
+            if normalized_type == 'eMNA' and match_name is None:
+                match_name = decode_string(prop.data)
+
+        kind_code = plugin_kind_code(self.properties)
+        kind = PLUGIN_KINDS.get(kind_code, kind_code.decode('mac_roman')) if kind_code else None
+
+        return PiplSummary(
+            match_name=match_name,
+            version_info=self.version_info,
+            category=PluginMetadata.from_properties(self.properties).category,
+            kind=kind,
+            property_count=len(self.properties),
+            format=self.format,
+            property_keys=sorted(prop.kind_code().decode('mac_roman') for prop in self.properties)
+        )
+
+@dataclass
+class TaggedVersionInfo:
+    """An effect version together with the file and resource it was read from."""
+    path: str
+    resource_id: Optional[int]  # None when the PiPL wasn't located through a resource map
+    version: VersionInfo
+
+    @property
+    def location(self) -> str:
+        """The file path, with the PiPL resource ID when there is one."""
+        return f"{self.path} (PiPL {self.resource_id})" if self.resource_id is not None else self.path
+
+    def __str__(self):
+        return f"{self.location}: {self.version}"
+
+    def to_dict(self) -> Dict[str, Any]:
+        """Convert to a plain dict suitable for JSON or other serializers."""
+        return {
+            'path': self.path,
+            'resource_id': self.resource_id,
+            'version': self.version.to_dict()
+        }
+
+@dataclass
+class PluginMetadata:
+    """Display name, Effects menu category, match name, supported pixel modes and out flags of a plug-in."""
+    name: Optional[str] = None
+    category: Optional[str] = None
+    match_name: Optional[str] = None
+    supported_modes: Optional[SupportedModes] = None
+    outflags: Optional[int] = None  # PF_OutFlag bits, None when the PiPL has no eGLO property
+    outflags2: Optional[OutFlags2] = None  # None when the PiPL has no eGL2 property
+
+    def display_name(self) -> str:
+        """Return the name, falling back to the match name and then '<unknown>'."""
+        return self.name or self.match_name or '<unknown>'
+
+    def is_smart_fx(self) -> bool:
+        """Check whether the effect uses SmartFX rendering (PF_OutFlag2_SUPPORTS_SMART_RENDER)."""
+        return self.outflags2 is not None and OutFlags2.SUPPORTS_SMART_RENDER in self.outflags2
+
+    def shows_dialog(self) -> bool:
+        """Check whether the effect shows an options dialog when applied (PF_OutFlag_I_DO_DIALOG)."""
+        return self.outflags is not None and bool(self.outflags & OUT_FLAG_I_DO_DIALOG)
+
+    def supports_16bit(self) -> bool:
+        """Check whether the effect renders 16 bpc, by its smod modes or PF_OutFlag_DEEP_COLOR_AWARE."""
+        return ((self.supported_modes is not None and SupportedModes.ARGB16 in self.supported_modes)
+                or (self.outflags is not None and bool(self.outflags & OUT_FLAG_DEEP_COLOR_AWARE)))
+
+    def supports_float(self) -> bool:
+        """Check whether the effect renders 32 bpc float, by its smod modes or PF_OutFlag2_FLOAT_COLOR_AWARE."""
+        return ((self.supported_modes is not None and SupportedModes.ARGB32F in self.supported_modes)
+                or (self.outflags2 is not None and OutFlags2.FLOAT_COLOR_AWARE in self.outflags2))
+
+    def to_dict(self) -> Dict[str, Any]:
+        """Convert to a plain dict suitable for JSON, with bitmasks as lists of flag names."""
+        return {
+            'name': self.name,
+            'category': self.category,
+            'match_name': self.match_name,
+            'supported_modes': self.supported_modes.mode_names() if self.supported_modes is not None else None,
+            'outflags': self.outflags,
+            'outflags2': self.outflags2.flag_names() if self.outflags2 is not None else None
+        }
+
+    @classmethod
+    def from_properties(cls, properties: List[PiplProperty]) -> 'PluginMetadata':
+        """Read the name, catg and eMNA strings and the smod, eGLO and eGL2 bitmasks."""
+        metadata = cls()
+        for prop in properties:
+            normalized_type = normalize_property_type(prop.property_type)
+            if normalized_type == 'name' and metadata.name is None:
+                metadata.name = decode_string(prop.data)
+            elif normalized_type == 'catg' and metadata.category is None:
+                metadata.category = decode_string(prop.data)
+            elif normalized_type == 'eMNA' and metadata.match_name is None:
+                metadata.match_name = decode_match_name(prop.data)
+            elif normalized_type == 'smod' and metadata.supported_modes is None and len(prop.data) >= 2:
+                metadata.supported_modes = decode_supported_modes(prop.data)
+            elif normalized_type == 'eGLO' and metadata.outflags is None and len(prop.data) >= 4:
+                metadata.outflags = struct.unpack('>I', prop.data[:4])[0]
+            elif normalized_type == 'eGL2' and metadata.outflags2 is None and len(prop.data) >= 4:
+                metadata.outflags2 = decode_outflags2(prop.data)
+        return metadata
+
+@dataclass
+class PiplSummary:
+    """Flattened snapshot of the key metadata in one PiPL, used for JSON output."""
+    match_name: Optional[str]
+    version_info: Optional[VersionInfo]
+    category: Optional[str]
+    kind: Optional[str]  # Name from PLUGIN_KINDS, or the raw 4-character code if unknown
+    property_count: int
+    format: str
+    property_keys: List[str] = field(default_factory=list)  # Sorted canonical keys of every property
+
+    def to_dict(self) -> Dict[str, Any]:
+        """Convert to a plain dict suitable for JSON or other serializers."""
+        return {
+            'match_name': self.match_name,
+            'version_info': self.version_info.to_dict() if self.version_info else None,
+            'category': self.category,
+            'kind': self.kind,
+            'property_count': self.property_count,
+            'format': self.format,
+            'property_keys': self.property_keys
+        }
@@ -0,0 +1,55 @@
+"""Incremental index of the plug-ins in a directory, so unchanged plug-ins aren't parsed again."""
+
+import json
+import os
+from typing import Dict, List
+
+from pipl_types import PiplEntry, PluginMetadata
+
+CACHE_FORMAT_VERSION = 1
+
+def plugin_mtime_ns(path: str) -> int:
+    """Return the modification time of a file, or the latest one of any file inside a bundle directory."""
+    if not os.path.isdir(path):
+        return os.stat(path).st_mtime_ns
+
+    latest = os.stat(path).st_mtime_ns
+    for root, _, files in os.walk(path):
+        for name in files:
+            latest = max(latest, os.stat(os.path.join(root, name)).st_mtime_ns)
+    return latest
+
+def build_cache_entry(mtime_ns: int, entries: List[PiplEntry]) -> Dict:
+    """Build the cache entry for one plug-in from its parsed PiPL entries."""
+    return {
+        'mtime_ns': mtime_ns,
+        'entries': [{
+            'resource_id': entry.resource_id,
+            'version_info': entry.version_info.to_dict() if entry.version_info else None,
+            'metadata': PluginMetadata.from_properties(entry.properties).to_dict()
+        } for entry in entries]
+    }
+
+def load_cache(cache_path: str) -> Dict[str, Dict]:
+    """Read the entries of a cache file, keyed by path relative to the indexed directory.
+
+    Caches written by a different format version are ignored rather than trusted.
+    """
+    with open(cache_path, 'r', encoding='utf-8') as f:
+        cache = json.load(f)
+
+    if cache.get('cache_version') != CACHE_FORMAT_VERSION:
+        return {}
+    return cache.get('plugins', {})
+
+def write_cache(cache_path: str, directory: str, index: Dict[str, Dict]) -> None:
+    """Write the cache entries of every plug-in in a directory."""
+    cache = {
+        'cache_version': CACHE_FORMAT_VERSION,
+        'directory': directory,
+        'plugins': index
+    }
+
+    with open(cache_path, 'w', encoding='utf-8') as f:
+        json.dump(cache, f, indent=2)
+        f.write('\n')
@@ -0,0 +1,80 @@
+"""Lockfile support for detecting unintended changes to a set of plug-ins."""
+
+import hashlib
+import json
+import os
+from typing import Dict, List, Optional
+
+from pipl_types import TaggedVersionInfo, VersionInfo
+
+LOCKFILE_FORMAT_VERSION = 1
+
+def file_sha256(path: str) -> str:
+    """Hash a file, or every file inside a bundle directory in a stable order."""
+    digest = hashlib.sha256()
+
+    if os.path.isdir(path):
+        for root, dirs, files in os.walk(path):
+            dirs.sort()
+            for name in sorted(files):
+                file_path = os.path.join(root, name)
+                digest.update(os.path.relpath(file_path, path).encode('utf-8'))
+                with open(file_path, 'rb') as f:
+                    for chunk in iter(lambda: f.read(65536), b''):
+                        digest.update(chunk)
+    else:
+        with open(path, 'rb') as f:
+            for chunk in iter(lambda: f.read(65536), b''):
+                digest.update(chunk)
+
+    return digest.hexdigest()
+
+def build_lock_entry(path: str, version_info: Optional[VersionInfo]) -> Dict:
+    """Build the lockfile entry for one plug-in."""
+    return {
+        'path': path,
+        'sha256': file_sha256(path),
+        'version_info': version_info.to_dict() if version_info else None
+    }
+
+def _first_version(path: str, versions: List[TaggedVersionInfo]) -> Optional[VersionInfo]:
+    return next((version.version for version in versions if version.path == path), None)
+
+def write_lockfile(lockfile_path: str, paths: List[str], versions: List[TaggedVersionInfo]) -> None:
+    """Write a lockfile with the checksum and version of every scanned plug-in."""
+    lockfile = {
+        'lockfile_version': LOCKFILE_FORMAT_VERSION,
+        'plugins': [build_lock_entry(path, _first_version(path, versions)) for path in paths]
+    }
+
+    with open(lockfile_path, 'w', encoding='utf-8') as f:
+        json.dump(lockfile, f, indent=2)
+        f.write('\n')
+
+def check_lockfile(lockfile_path: str, paths: List[str], versions: List[TaggedVersionInfo]) -> List[str]:
+    """Compare scanned plug-ins against a lockfile and return a description of each difference."""
+    with open(lockfile_path, 'r', encoding='utf-8') as f:
+        lockfile = json.load(f)
+
+    locked = {entry['path']: entry for entry in lockfile.get('plugins', [])}
+    problems = []
+
+    for path in paths:
+        current = build_lock_entry(path, _first_version(path, versions))
+        expected = locked.pop(path, None)
+
+        if expected is None:
+            problems.append(f"{path}: not in lockfile")
+            continue
+
+        if current['version_info'] != expected['version_info']:
+            old_version = VersionInfo.from_dict(expected['version_info']) if expected['version_info'] else None
+            new_version = VersionInfo.from_dict(current['version_info']) if current['version_info'] else None
+            problems.append(f"{path}: version changed from {old_version} to {new_version}")
+        elif current['sha256'] != expected['sha256']:
+            problems.append(f"{path}: checksum changed")
+
+    for path in locked:
+        problems.append(f"{path}: in lockfile but not scanned")
+
+    return problems
@@ -5,7 +5,7 @@ from typing import List, Dict
 from pipl_types import (
     PiplProperty, PLUGIN_KINDS, AE_OUT_FLAGS, AE_OUT_FLAGS_2,
     decode_flags, decode_version, decode_string, decode_entry_point,
-    decode_effect_version
+    decode_effect_version, decode_supported_modes, normalize_property_type
 )
 
 class RGenerator:
@@ -20,23 +20,7 @@ class RGenerator:
 
     def _normalize_property_type(self, prop_type: str) -> str:
         """Normalize property types from different sources (direct, reversed, Windows)."""
-        # Mapping for reversed property types (from RCP/AEX)
-        type_mappings = {
-            'dnik': 'kind',
-            'eman': 'name',
-            'gtac': 'catg',
-            '4668': '8664',
-            'ANMe': 'eMNA',
-            'RVPe': 'ePVR',
-            'RVSe': 'eSVR',
-            'REVe': 'eVER',
-            'FNIe': 'eINF',
-            'OLGe': 'eGLO',
-            '2LGe': 'eGL2',
-            'LFea': 'aeFL'
-        }
-
-        return type_mappings.get(prop_type, prop_type)
+        return normalize_property_type(prop_type)
 
     def _extract_basic_info(self) -> None:
         """Extract basic plugin information from properties."""
@@ -149,6 +133,20 @@ class RGenerator:
                 reserved = 8
             return f"[{index}] AE_Reserved_Info [{normalized_type}]: {reserved}"
 
+        elif normalized_type == 'Flg ':
+            # Effect Flags (non-standard, bit meanings are not documented)
+            if len(prop.data) >= 4:
+                flags = struct.unpack('>I', prop.data[:4])[0]
+                return f"[{index}] AE_Effect_Flags [{normalized_type}]: {flags:#010x} ({flags:032b})"
+            return f"[{index}] AE_Effect_Flags [{normalized_type}]: <Error while parsing...>"
+
+        elif normalized_type == 'smod':
+            # Supported pixel modes
+            if len(prop.data) >= 2:
+                modes = decode_supported_modes(prop.data)
+                return f"[{index}] Supported_Modes [{normalized_type}]: {int(modes):#06x} ({', '.join(modes.mode_names()) or 'none'})"
+            return f"[{index}] Supported_Modes [{normalized_type}]: <Error while parsing...>"
+
         else:
             # Unknown property
             data_hex = prop.data[:16].hex() if prop.data else "00"
@@ -1,8 +1,86 @@
 """Parser for macOS resource fork (.rsrc) files containing PIPL data."""
 
+import logging
+import mmap
 import struct
-from typing import List, Dict, Optional, Tuple, Any
-from pipl_types import PiplProperty, PIPL_PROPERTY_TYPES
+from dataclasses import dataclass
+from typing import BinaryIO, List, Dict, Optional, Tuple, Any, TextIO
+from pipl_types import (
+    MAX_PIPL_PROPERTIES, FileTooSmallError, MalformedChunkError, PiplEntry, PiplError, PiplProperty, PIPL_DEFAULT_RESOURCE_ID, PIPL_PROPERTY_TYPES,
+    PIPL_TYPE_CODE, TooManyPropertiesError, TruncatedDataError, UnexpectedEofError, VersionSource, os_type_to_str,
+    parse_vers_resource, require_bytes
+)
+
+logger = logging.getLogger(__name__)
+
+PIPL_RESOURCE_TYPE = os_type_to_str(PIPL_TYPE_CODE)
+
+# The resource map starts with a copy of the 16-byte fork header, a 4-byte next map
+# handle, a 2-byte file reference number and 2 bytes of attributes, followed by the
+# 2-byte offsets of the type list and name list (relative to the map)
+RESOURCE_MAP_TYPE_LIST_OFFSET_FIELD = 24
+RESOURCE_MAP_NAME_LIST_OFFSET_FIELD = 26
+RESOURCE_MAP_HEADER_SIZE = 28
+
+@dataclass
+class ParseConfig:
+    """Options controlling how resource fork data is parsed."""
+    # Fall back to scanning for 8BIM properties when the resource map can't be read,
+    # and only raise the resource map error if that finds nothing either
+    retry_on_format_error: bool = True
+    # Reject PiPLs with more properties than this, which only a corrupt file has
+    max_properties: int = MAX_PIPL_PROPERTIES
+
+@dataclass
+class ResourceEntry:
+    """A single resource listed in a resource fork's map."""
+    resource_type: str  # 4-character type code like 'PiPL'
+    resource_id: int
+    name_offset: int  # Offset into the name list, 0xFFFF when the resource is unnamed
+    attributes: int
+    data_offset: int  # Absolute offset of the resource bytes (after the length prefix)
+    data_length: int
+    name: Optional[str] = None  # Decoded from the name list, None when the resource is unnamed
+
+    def read_data(self, fork_data: bytes) -> memoryview:
+        """Return a view of this resource's bytes inside the original fork data without copying."""
+        end = self.data_offset + self.data_length
+        if end > len(fork_data):
+            raise TruncatedDataError(
+                f"Resource '{self.resource_type}' {self.resource_id} spans bytes "
+                f"{self.data_offset}-{end} but the fork is only {len(fork_data)} bytes"
+            )
+        return memoryview(fork_data)[self.data_offset:end]
+
+@dataclass
+class ResourceTypeSummary:
+    """Number of resources and bytes of data stored under one resource type."""
+    resource_type: str
+    resource_count: int
+    data_bytes: int
+
+    @staticmethod
+    def most_common_type(summaries: List['ResourceTypeSummary']) -> Optional['ResourceTypeSummary']:
+        """Return the type with the most resources, or None if there are no types."""
+        return max(summaries, key=lambda summary: summary.resource_count, default=None)
+
+def summarize_resource_types(entries: List[ResourceEntry]) -> List[ResourceTypeSummary]:
+    """Group resource map entries by type, in the order the types are listed."""
+    summaries: Dict[str, ResourceTypeSummary] = {}
+    for entry in entries:
+        summary = summaries.setdefault(entry.resource_type, ResourceTypeSummary(entry.resource_type, 0, 0))
+        summary.resource_count += 1
+        summary.data_bytes += entry.data_length
+
+    return list(summaries.values())
+
+def total_resource_count(summaries: List[ResourceTypeSummary]) -> int:
+    """Total number of resources across all types."""
+    return sum(summary.resource_count for summary in summaries)
+
+def total_data_bytes(summaries: List[ResourceTypeSummary]) -> int:
+    """Total bytes of resource data across all types."""
+    return sum(summary.data_bytes for summary in summaries)
 
 class ResourceForkParser:
     """Parse macOS resource fork files to extract PIPL data."""
@@ -10,13 +88,38 @@ class ResourceForkParser:
     def __init__(self, file_path: str):
         self.file_path = file_path
         self.data = b''
+        # Offset of self.data within file_path, or None when the data didn't come straight from a file
+        self.base_offset: Optional[int] = 0
         self._load_file()
 
+    @classmethod
+    def from_bytes(cls, data: bytes, file_path: str = '<memory>',
+                   base_offset: Optional[int] = None) -> 'ResourceForkParser':
+        """Create a parser over resource fork data that is already in memory.
+
+        Pass base_offset when the data was read from file_path at that offset, so
+        parsed properties can report where their data lives in the file.
+        """
+        parser = cls.__new__(cls)
+        parser.file_path = file_path
+        parser.data = data
+        parser.base_offset = base_offset
+        return parser
+
+    def _file_offset(self, offset: int) -> Optional[int]:
+        """Convert an offset into self.data to an offset into the file, if known."""
+        return None if self.base_offset is None else self.base_offset + offset
+
     def _load_file(self) -> None:
-        """Load the entire resource file into memory."""
+        """Memory-map the resource file, falling back to reading it into memory."""
         try:
             with open(self.file_path, 'rb') as f:
-                self.data = f.read()
+                try:
+                    # Read-only mapping avoids copying large files onto the heap
+                    self.data = mmap.mmap(f.fileno(), 0, access=mmap.ACCESS_READ)
+                except (ValueError, OSError):
+                    # Empty files and some filesystems can't be mapped
+                    self.data = f.read()
         except FileNotFoundError:
             raise FileNotFoundError(f"Resource file not found: {self.file_path}")
         except Exception as e:
@@ -24,79 +127,196 @@ class ResourceForkParser:
 
     def _read_big_endian_uint32(self, offset: int) -> int:
         """Read a big-endian 32-bit unsigned integer."""
-        if offset + 4 > len(self.data):
-            raise ValueError(f"Cannot read uint32 at offset {offset}")
+        require_bytes(self.data, offset, 4)
         return struct.unpack('>I', self.data[offset:offset+4])[0]
 
     def _read_big_endian_uint16(self, offset: int) -> int:
         """Read a big-endian 16-bit unsigned integer."""
-        if offset + 2 > len(self.data):
-            raise ValueError(f"Cannot read uint16 at offset {offset}")
+        require_bytes(self.data, offset, 2)
         return struct.unpack('>H', self.data[offset:offset+2])[0]
 
+    def get_resource_entries(self) -> List[ResourceEntry]:
+        """Parse the resource map and return every resource it lists."""
+        entries = []
+
+        header = ResourceForkHeader.from_bytes(self.data)
+        data_offset = header.data_offset
+        map_offset = header.map_offset
+
+        # Offsets of the type list and name list are relative to the start of the map
+        type_list_offset = map_offset + self._read_big_endian_uint16(map_offset + RESOURCE_MAP_TYPE_LIST_OFFSET_FIELD)
+        name_list_offset = map_offset + self._read_big_endian_uint16(map_offset + RESOURCE_MAP_NAME_LIST_OFFSET_FIELD)
+
+        # The type count is stored minus one, so 0xFFFF means the map is empty
+        num_types = (self._read_big_endian_uint16(type_list_offset) + 1) & 0xFFFF
+
+        for i in range(num_types):
+            type_offset = type_list_offset + 2 + (i * 8)  # Each type entry is 8 bytes
+            require_bytes(self.data, type_offset, 8)
+            type_entry = ResourceType(
+                type_code=bytes(self.data[type_offset:type_offset + 4]),
+                resource_count=self._read_big_endian_uint16(type_offset + 4) + 1,
+                reference_list_offset=type_list_offset + self._read_big_endian_uint16(type_offset + 6)
+            )
+            entries.extend(parse_resource_entries(self.data, type_entry, data_offset, name_list_offset))
+
+        return entries
+
+    def summarize_resource_types(self) -> List[ResourceTypeSummary]:
+        """Group the resource map by type, in the order the types are listed."""
+        return summarize_resource_types(self.get_resource_entries())
+
+    def resource_data(self, resource_type: str, resource_id: int) -> Optional[memoryview]:
+        """Return the bytes of a resource by type and ID, or None if the map doesn't list it."""
+        for entry in self.get_resource_entries():
+            if entry.resource_type == resource_type and entry.resource_id == resource_id:
+                return entry.read_data(self.data)
+
+        return None
+
     def _find_pipl_in_binary(self) -> List[Dict]:
         """Find PIPL data in binary by looking for 8BIM signatures."""
         pipl_data_blocks = []
         offset = 0
 
-        while offset < len(self.data) - 12:
+        # Stop once a full signature, key, reserved and length header no longer fits,
+        # so reading those fields can't run past the end
+        while offset + 16 <= len(self.data):
             # Look for '8BIM' signature
-            if self.data[offset:offset+4] == b'8BIM':
-                # Found potential PIPL property
-                try:
-                    property_type = self.data[offset+4:offset+8]
-
-                    # Skip 4 null bytes (standard in this format)
-                    null_bytes_offset = offset + 8
-                    if null_bytes_offset + 4 <= len(self.data):
-                        # Read length at offset + 12
-                        length_offset = null_bytes_offset + 4
-                        if length_offset + 4 <= len(self.data):
-                            length = self._read_big_endian_uint32(length_offset)
-                            data_start = length_offset + 4
-
-                            if data_start + length <= len(self.data):
-                                property_data = self.data[data_start:data_start + length]
-                                pipl_data_blocks.append({
-                                    'type': property_type,
-                                    'length': length,
-                                    'data': property_data
-                                })
-                                # Move to next property (align to next 8BIM)
-                                offset = data_start + length
-                            else:
-                                offset += 1
-                        else:
-                            offset += 1
-                    else:
-                        offset += 1
-                except Exception as e:
-                    offset += 1
-            else:
+            if self.data[offset:offset+4] != b'8BIM':
+                offset += 1
+                continue
+
+            # Skip the key and 4 null bytes (standard in this format), then read the length
+            property_type = self.data[offset+4:offset+8]
+            length = self._read_big_endian_uint32(offset + 12)
+            data_start = offset + 16
+
+            try:
+                require_bytes(self.data, data_start, length)
+            except UnexpectedEofError:
+                # Data running past the end means this '8BIM' isn't a property header
                 offset += 1
+                continue
+
+            pipl_data_blocks.append({
+                'type': property_type,
+                'length': length,
+                'data': self.data[data_start:data_start + length],
+                'data_offset': data_start
+            })
+            # Move to next property (align to next 8BIM)
+            offset = data_start + length
 
         return pipl_data_blocks
 
-    def parse_pipl_properties(self) -> List[PiplProperty]:
+    def parse_pipl_properties(self, max_properties: int = MAX_PIPL_PROPERTIES) -> List[PiplProperty]:
         """Parse PIPL properties from the resource fork."""
         properties = []
 
         # First try to find PIPL data by looking for 8BIM signatures
         pipl_blocks = self._find_pipl_in_binary()
+        if len(pipl_blocks) > max_properties:
+            raise TooManyPropertiesError(len(pipl_blocks), max_properties)
 
         for block in pipl_blocks:
             prop_type = block['type'].decode('ascii', errors='ignore')
+            data = block['data']
+            length = block['length']
+            data_offset = block['data_offset']
+
+            if block['type'] == b'eVER':
+                if length == 8:
+                    # Some files put a 4-byte secondary header before the version value
+                    data = data[4:]
+                    length = 4
+                    data_offset += 4
+                elif length != 4:
+                    raise MalformedChunkError(f"eVER chunk has length {length}, expected 4 or 8")
 
             # Map property type codes
             if block['type'] in PIPL_PROPERTY_TYPES:
                 properties.append(PiplProperty(
                     property_type=prop_type,
-                    data=block['data'],
-                    length=block['length']
+                    data=data,
+                    length=length,
+                    data_offset=self._file_offset(data_offset)
                 ))
 
         return properties
 
+    def find_8bim_versions(self) -> List[Tuple[int, int]]:
+        """Return the (file offset, encoded value) of every eVER chunk found by the 8BIM scan.
+
+        Files can contain several concatenated plugin descriptors, each with its own eVER.
+        """
+        return [(prop.data_offset, struct.unpack('>I', bytes(prop.data[:4]))[0])
+                for prop in self.parse_pipl_properties() if prop.property_type == 'eVER']
+
+    def parse_pipl_entries(self, config: Optional[ParseConfig] = None) -> List[PiplEntry]:
+        """Parse every PiPL resource listed in the resource map.
+
+        Forks without a PiPL fall back to the version in a classic 'vers' resource.
+        Files without a usable resource map (such as a bare 8BIM property stream)
+        are scanned as a whole and yield a single entry without a resource ID.
+        """
+        config = config or ParseConfig()
+        map_error = None
+
+        # Shorter data holds neither a resource fork header nor a single property header
+        if len(self.data) < RESOURCE_FORK_HEADER_STRUCT.size:
+            raise FileTooSmallError(len(self.data), RESOURCE_FORK_HEADER_STRUCT.size)
+
+        logger.debug(f"Reading resource map of {self.file_path}")
+        try:
+            resource_entries = self.get_resource_entries()
+            # First find the raw PiPL resources, then parse the properties of each
+            pipl_blobs = extract_pipl_bytes(self.data, resource_entries)
+            resource_data = [(entry.resource_id, entry.data_offset, data)
+                             for entry, data in zip(pipl_resource_entries(resource_entries), pipl_blobs)]
+        except (ValueError, PiplError) as e:
+            if not config.retry_on_format_error:
+                raise
+            logger.debug(f"Could not read resource map of {self.file_path}: {e}")
+            map_error = e
+            resource_entries = []
+            resource_data = []
+
+        entries = []
+        for resource_id, data_offset, data in resource_data:
+            resource_parser = ResourceForkParser.from_bytes(data, self.file_path, self._file_offset(data_offset))
+            properties = resource_parser.parse_pipl_properties(config.max_properties)
+            if properties:
+                entries.append(PiplEntry.from_properties(properties, 'resource_fork', resource_id))
+
+        if not any(entry.resource_type == PIPL_RESOURCE_TYPE for entry in resource_entries):
+            # Classic Mac OS plug-ins may only carry a 'vers' resource
+            vers_entry = next((entry for entry in resource_entries
+                               if entry.resource_type == 'vers' and entry.resource_id == 1), None)
+            if vers_entry:
+                try:
+                    version_info = parse_vers_resource(bytes(vers_entry.read_data(self.data)))
+                    return [PiplEntry(resource_id=1, format='resource_fork', version_info=version_info,
+                                      properties=[], version_source=VersionSource.VERS)]
+                except (ValueError, PiplError):
+                    pass
+
+        if not entries:
+            logger.debug(f"Scanning {self.file_path} for 8BIM properties")
+            properties = self.parse_pipl_properties(config.max_properties)
+            versions = [prop for prop in properties if prop.property_type == 'eVER']
+            if len(versions) > 1:
+                offsets = ', '.join(f"{prop.data_offset:#x}" if prop.data_offset is not None else '?'
+                                    for prop in versions)
+                logger.warning(f"Warning: Found {len(versions)} eVER chunks in {self.file_path} "
+                               f"(at {offsets}); using the first")
+            if properties:
+                entries.append(PiplEntry.from_properties(properties, '8bim'))
+            elif map_error:
+                raise map_error
+
+        return entries
+
     def extract_resource_data(self) -> Optional[bytes]:
         """Extract raw resource data for debugging purposes."""
         if not self.data:
@@ -110,26 +330,264 @@ class ResourceForkParser:
             # Return data starting from PIPL marker
             return self.data[pipl_pos:]
 
-        return self.data
+        return self.data[:]
+
+    def _count_occurrences(self, needle: bytes) -> int:
+        """Count non-overlapping occurrences of a byte sequence (works for bytes and mmap)."""
+        count = 0
+        position = self.data.find(needle)
+        while position >= 0:
+            count += 1
+            position = self.data.find(needle, position + len(needle))
+        return count
 
     def get_file_info(self) -> Dict:
         """Get basic information about the resource file."""
         return {
             'file_path': self.file_path,
             'file_size': len(self.data),
-            'has_pipl_marker': b'PiPL' in self.data,
-            'has_8bim_signatures': b'8BIM' in self.data,
-            'num_8bim_blocks': self.data.count(b'8BIM')
+            'has_pipl_marker': self.data.find(b'PiPL') >= 0,
+            'has_8bim_signatures': self.data.find(b'8BIM') >= 0,
+            'num_8bim_blocks': self._count_occurrences(b'8BIM')
         }
 
     def debug_hex_dump(self, start: int = 0, length: int = 256) -> str:
         """Create a hex dump of the file for debugging."""
         end = min(start + length, len(self.data))
-        result = []
+        return hex_dump(self.data[start:end], start)
+
+# Data offset, map offset, data length and map length, compiled once and unpacked
+# straight from the buffer so mmapped forks aren't sliced
+RESOURCE_FORK_HEADER_STRUCT = struct.Struct('>IIII')
+
+@dataclass
+class ResourceForkHeader:
+    """The 16-byte header at the start of a resource fork."""
+    data_offset: int
+    map_offset: int
+    data_length: int
+    map_length: int
+
+    @classmethod
+    def from_bytes(cls, data: bytes) -> 'ResourceForkHeader':
+        require_bytes(data, 0, RESOURCE_FORK_HEADER_STRUCT.size)
+        return cls(*RESOURCE_FORK_HEADER_STRUCT.unpack_from(data))
+
+@dataclass
+class ResourceType:
+    """One entry of a resource map's type list."""
+    type_code: bytes
+    resource_count: int
+    reference_list_offset: int  # Absolute offset of the type's reference list
+
+    @property
+    def type_code_str(self) -> str:
+        return self.type_code.decode('mac_roman')
+
+def read_resource_name(data: bytes, name_list_base: int, name_offset: int) -> Optional[str]:
+    """Decode a resource's Pascal string name from the name list.
+
+    Returns None for unnamed resources (name offset 0xFFFF) and for names that
+    run past the end of the data, so a damaged name list doesn't hide the resources.
+    """
+    if name_offset == 0xFFFF:
+        return None
+
+    offset = name_list_base + name_offset
+    if offset >= len(data) or offset + 1 + data[offset] > len(data):
+        return None
+    return bytes(data[offset + 1:offset + 1 + data[offset]]).decode('mac_roman')
+
+def parse_resource_entries(data: bytes, type_entry: 'ResourceType', data_offset: int,
+                           name_list_offset: Optional[int] = None) -> List[ResourceEntry]:
+    """Read the reference list of one resource type.
+
+    data_offset is the start of the fork's data section, which the 24-bit
+    offsets in the references are relative to. Names are read when the
+    absolute name_list_offset is given.
+    """
+    entries = []
+
+    for i in range(type_entry.resource_count):
+        reference_offset = type_entry.reference_list_offset + (i * 12)  # Each reference is 12 bytes
+        require_bytes(data, reference_offset, 8)
+        resource_id, name_offset, attributes_and_offset = struct.unpack_from('>hHI', data, reference_offset)
+
+        # Attributes share a 32-bit field with the 24-bit data offset
+        resource_offset = data_offset + (attributes_and_offset & 0x00FFFFFF)
+        require_bytes(data, resource_offset, 4)
+
+        entries.append(ResourceEntry(
+            resource_type=type_entry.type_code_str,
+            resource_id=resource_id,
+            name_offset=name_offset,
+            attributes=attributes_and_offset >> 24,
+            data_offset=resource_offset + 4,
+            data_length=struct.unpack_from('>I', data, resource_offset)[0],
+            name=read_resource_name(data, name_list_offset, name_offset) if name_list_offset is not None else None
+        ))
+
+    return entries
+
+class PiplForkReader:
+    """Read resources from a seekable resource fork stream on demand.
+
+    Only the header and type list are read up front, unlike ResourceForkParser
+    which needs the whole fork in memory.
+    """
+
+    def __init__(self, reader: BinaryIO, header: ResourceForkHeader, type_list: List[ResourceType]):
+        self.reader = reader
+        self.header = header
+        self.type_list = type_list
+
+    @classmethod
+    def open(cls, reader: BinaryIO) -> 'PiplForkReader':
+        """Read the header and type list from a binary stream."""
+        reader.seek(0)
+        header = ResourceForkHeader.from_bytes(reader.read(RESOURCE_FORK_HEADER_STRUCT.size))
+
+        type_list_field = cls._read_at(reader, header.map_offset + RESOURCE_MAP_TYPE_LIST_OFFSET_FIELD, 2)
+        type_list_offset = header.map_offset + struct.unpack('>H', type_list_field)[0]
+        num_types = (struct.unpack('>H', cls._read_at(reader, type_list_offset, 2))[0] + 1) & 0xFFFF
+
+        type_list = []
+        type_data = cls._read_at(reader, type_list_offset + 2, num_types * 8)
+        for i in range(num_types):
+            type_code, count, reference_offset = struct.unpack('>4sHH', type_data[i * 8:i * 8 + 8])
+            type_list.append(ResourceType(type_code, count + 1, type_list_offset + reference_offset))
+
+        return cls(reader, header, type_list)
+
+    @staticmethod
+    def _read_at(reader: BinaryIO, offset: int, length: int) -> bytes:
+        reader.seek(offset)
+        data = reader.read(length)
+        if len(data) != length:
+            raise TruncatedDataError(f"Expected {length} bytes at offset {offset}, got {len(data)}")
+        return data
+
+    def resource_data(self, type_code: bytes, resource_id: int) -> Optional[bytes]:
+        """Seek to and read the bytes of a resource, or return None if the map doesn't list it."""
+        resource_type = next((entry for entry in self.type_list if entry.type_code == type_code), None)
+        if resource_type is None:
+            return None
+
+        references = self._read_at(self.reader, resource_type.reference_list_offset, resource_type.resource_count * 12)
+        for i in range(resource_type.resource_count):
+            reference_id, _, attributes_and_offset = struct.unpack('>hHI', references[i * 12:i * 12 + 8])
+            if reference_id == resource_id:
+                resource_offset = self.header.data_offset + (attributes_and_offset & 0x00FFFFFF)
+                length = struct.unpack('>I', self._read_at(self.reader, resource_offset, 4))[0]
+                return self._read_at(self.reader, resource_offset + 4, length)
+
+        return None
+
+APPLEDOUBLE_MAGIC = 0x00051607
+APPLEDOUBLE_RESOURCE_FORK_ENTRY = 2
+
+def is_appledouble(data: bytes) -> bool:
+    """Check for the AppleDouble header used by '._' files on non-HFS filesystems."""
+    return len(data) >= 4 and struct.unpack('>I', data[:4])[0] == APPLEDOUBLE_MAGIC
+
+def appledouble_resource_fork_range(data: bytes) -> Optional[Tuple[int, int]]:
+    """Return the (offset, length) of the resource fork in an AppleDouble file, or None if it has none.
+
+    The header is magic, version, 16 filler bytes and an entry count, followed by
+    12-byte (ID, offset, length) entry descriptors.
+    """
+    require_bytes(data, 0, 26)
+    num_entries = struct.unpack('>H', data[24:26])[0]
+    require_bytes(data, 26, num_entries * 12)
+
+    for i in range(num_entries):
+        entry_id, offset, length = struct.unpack('>III', data[26 + i * 12:38 + i * 12])
+        if entry_id == APPLEDOUBLE_RESOURCE_FORK_ENTRY:
+            require_bytes(data, offset, length)
+            return offset, length
+
+    return None
+
+def extract_appledouble_resource_fork(data: bytes) -> Optional[bytes]:
+    """Return the resource fork stored in an AppleDouble file, or None if it has none."""
+    fork_range = appledouble_resource_fork_range(data)
+    if fork_range is None:
+        return None
+    offset, length = fork_range
+    return bytes(data[offset:offset + length])
+
+def best_pipl_resource(data: bytes) -> Optional[Tuple[int, bytes]]:
+    """Pick the PiPL resource a host would most likely use and return its ID and bytes.
+
+    Prefers ID 16000, the ID After Effects SDK projects use for the effect PiPL.
+    Otherwise the highest ID wins, on the assumption that later variants get
+    higher IDs. Returns None if the fork has no PiPL resources.
+    """
+    parser = ResourceForkParser.from_bytes(data)
+    pipl_resources = [entry for entry in parser.get_resource_entries()
+                      if entry.resource_type == PIPL_RESOURCE_TYPE]
+    if not pipl_resources:
+        return None
+
+    best = next((entry for entry in pipl_resources if entry.resource_id == PIPL_DEFAULT_RESOURCE_ID),
+                max(pipl_resources, key=lambda entry: entry.resource_id))
+    return best.resource_id, bytes(best.read_data(data))
+
+def list_resource_types(data: bytes) -> List[ResourceTypeSummary]:
+    """List the resource types in a fork with the number and total size of their resources."""
+    return summarize_resource_types(ResourceForkParser.from_bytes(data).get_resource_entries())
+
+def pipl_resource_entries(entries: List[ResourceEntry]) -> List[ResourceEntry]:
+    """Filter a resource map down to its PiPL resources."""
+    return [entry for entry in entries if entry.resource_type == PIPL_RESOURCE_TYPE]
+
+def extract_pipl_bytes(rsrc_data: bytes, entries: Optional[List[ResourceEntry]] = None) -> List[bytes]:
+    """Return the raw bytes of every PiPL resource in a resource fork without parsing their properties.
+
+    Each blob can be passed to pipl_parser.parse_pipl_data or decoded some other way.
+    Pass the fork's resource map `entries` when they have already been read.
+    """
+    if entries is None:
+        entries = ResourceForkParser.from_bytes(rsrc_data).get_resource_entries()
+    return [bytes(entry.read_data(rsrc_data)) for entry in pipl_resource_entries(entries)]
+
+def hex_dump(data: bytes, base_offset: int = 0) -> str:
+    """Format bytes as an xxd-style dump of offset, hex bytes and printable ASCII."""
+    result = []
+
+    for i in range(0, len(data), 16):
+        chunk = data[i:i+16]
+        hex_part = ' '.join(f'{b:02x}' for b in chunk)
+        ascii_part = ''.join(chr(b) if 32 <= b < 127 else '.' for b in chunk)
+        result.append(f'{base_offset + i:08x}  {hex_part:<48} |{ascii_part}|')
+
+    return '\n'.join(result)
+
+def _escape_bytes(raw: bytes) -> str:
+    """Render bytes as text, escaping anything that isn't printable ASCII."""
+    return ''.join(chr(b) if 32 <= b < 127 else f'\\x{b:02x}' for b in raw)
+
+def dump_resource_map_ascii(data: bytes, out: TextIO) -> None:
+    """Write a table of every resource in a resource fork's map for debugging."""
+    parser = ResourceForkParser.from_bytes(data)
+    entries = parser.get_resource_entries()
+
+    out.write(f"{'Type':<16} {'ID':>6}  {'Name':<20} {'Length':>10} {'Offset':>10}\n")
+    out.write('-' * 68 + '\n')
+
+    total = 0
+    for entry in entries:
+        type_code = entry.resource_type.encode('mac_roman')
+        if all(32 <= b < 127 for b in type_code):
+            type_label = type_code.decode('ascii')
+        else:
+            type_label = '0x' + type_code.hex()
+
+        name_label = _escape_bytes(entry.name.encode('mac_roman'))[:20] if entry.name is not None else '-'
 
-        for i in range(start, end, 16):
-            hex_part = ' '.join(f'{b:02x}' for b in self.data[i:i+16])
-            ascii_part = ''.join(chr(b) if 32 <= b < 127 else '.' for b in self.data[i:i+16])
-            result.append(f'{i:08x}  {hex_part:<48} |{ascii_part}|')
+        out.write(f"{type_label:<16} {entry.resource_id:>6}  {name_label:<20} "
+                  f"{entry.data_length:>10} {entry.data_offset:>#10x}\n")
+        total += entry.data_length
 
-        return '\n'.join(result)
\ No newline at end of file
+    out.write('-' * 68 + '\n')
+    out.write(f"{len(entries)} resources, {total} bytes of resource data\n")
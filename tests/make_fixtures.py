@@ -0,0 +1,54 @@
+"""Generate the binary fixtures used by the integration tests.
+
+Run from the repository root with `python3 -m tests.make_fixtures`.
+"""
+
+import os
+
+from pipl_builder import PiplBuilder, build_resource_fork
+from pipl_types import Stage, VersionInfo
+
+FIXTURES_DIR = os.path.join(os.path.dirname(__file__), 'fixtures')
+
+def build_fixtures():
+    """Return a mapping of fixture file name to its bytes."""
+    glow = (PiplBuilder()
+            .add_raw(b'kind', b'eFKT')
+            .add_name('Glow')
+            .add_category('Stylize')
+            .add_version(VersionInfo(5, 14, 0, Stage.RELEASE, 3))
+            .add_raw(b'eMNA', b'\x09ADBE Glow'))
+
+    blur = (PiplBuilder()
+            .add_raw(b'kind', b'eFKT')
+            .add_name('Soft Blur')
+            .add_category('Blur & Sharpen')
+            .add_version(VersionInfo(1, 2, 3, Stage.BETA, 42)))
+
+    noise = (PiplBuilder()
+             .add_raw(b'kind', b'eFKT')
+             .add_name('Noise')
+             .add_version(VersionInfo(2, 0, 1, Stage.ALPHA, 7)))
+
+    return {
+        # Mac resource fork with a single PiPL resource
+        'mac_single.rsrc': build_resource_fork([(b'PiPL', 16000, glow.build_mac())]),
+        # Mac resource fork with a PiPL alongside unrelated resource types
+        'mac_multi_type.rsrc': build_resource_fork([
+            (b'STR ', 128, b'\x0bSoft Blur FX'),
+            (b'PiPL', 16000, blur.build_mac()),
+            (b'vers', 1, b'\x01\x02\x60\x2a\x00\x00')
+        ]),
+        # Bare 8BIM property stream without a resource fork around it
+        'bim_stream.rsrc': noise.build_mac()
+    }
+
+def main():
+    os.makedirs(FIXTURES_DIR, exist_ok=True)
+    for name, data in build_fixtures().items():
+        with open(os.path.join(FIXTURES_DIR, name), 'wb') as f:
+            f.write(data)
+        print(f"Wrote {name} ({len(data)} bytes)")
+
+if __name__ == '__main__':
+    main()
@@ -0,0 +1,178 @@
+"""Run the command-line tool on bad input and check it fails cleanly."""
+
+import json
+import os
+import struct
+import subprocess
+import sys
+import tempfile
+import unittest
+
+from ae_pipl_extractor import EXIT_ERROR, EXIT_UNKNOWN_FORMAT, HFS_VOLUME_HEADER_OFFSET
+from pipl_builder import PiplBuilder
+from pipl_types import Stage, VersionInfo
+from tests.make_fixtures import FIXTURES_DIR
+
+SCRIPT = os.path.join(os.path.dirname(os.path.dirname(os.path.abspath(__file__))), 'ae_pipl_extractor.py')
+
+def run_cli(*args: str) -> subprocess.CompletedProcess:
+    return subprocess.run([sys.executable, SCRIPT, *args], capture_output=True, text=True)
+
+def thin_macho_with_rsrc(cpu_type: int, rsrc: bytes) -> bytes:
+    """A little-endian 64-bit Mach-O image with one segment holding a __rsrc section."""
+    data_offset = 32 + 72 + 80
+    header = struct.pack('<IiiIIIII', 0xFEEDFACF, cpu_type, 0, 0x8, 1, 72 + 80, 0, 0)
+    segment = struct.pack('<II16sQQQQiiII', 0x19, 72 + 80, b'__DATA', 0, len(rsrc), data_offset, len(rsrc), 3, 3, 1, 0)
+    section = struct.pack('<16s16sQQIIIIIIII', b'__rsrc', b'__DATA', 0, len(rsrc), data_offset, 0, 0, 0, 0, 0, 0, 0)
+    return header + segment + section + rsrc
+
+def universal_macho(*images):
+    """A fat binary holding the (cpu_type, image) slices one after another."""
+    offset = 8 + 20 * len(images)
+    arches, body = b'', b''
+    for cpu_type, image in images:
+        arches += struct.pack('>iiIII', cpu_type, 0, offset + len(body), len(image), 0)
+        body += image
+    return struct.pack('>II', 0xCAFEBABE, len(images)) + arches + body
+
+class LockfileErrorTest(unittest.TestCase):
+    """Lockfile problems are reported as errors rather than tracebacks."""
+
+    def setUp(self):
+        self.tmp = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmp.cleanup)
+        self.fixture = os.path.join(FIXTURES_DIR, 'mac_single.rsrc')
+
+    def test_missing_lockfile(self):
+        result = run_cli('-q', '--check-lockfile', os.path.join(self.tmp.name, 'nope.json'), self.fixture)
+        self.assertEqual(result.returncode, EXIT_ERROR)
+        # Log messages go to stdout in text mode
+        self.assertIn("Could not read lockfile", result.stdout)
+        self.assertNotIn('Traceback', result.stderr)
+
+    def test_missing_input_is_left_out(self):
+        lockfile = os.path.join(self.tmp.name, 'plugins.lock')
+        missing = os.path.join(self.tmp.name, 'missing.rsrc')
+        result = run_cli('-q', '--write-lockfile', lockfile, self.fixture, missing)
+        self.assertEqual(result.returncode, EXIT_ERROR)
+        self.assertNotIn('Traceback', result.stderr)
+        with open(lockfile, encoding='utf-8') as f:
+            contents = f.read()
+        self.assertIn('mac_single.rsrc', contents)
+        self.assertNotIn('missing.rsrc', contents)
+
+class DiffErrorTest(unittest.TestCase):
+    """--diff reports files that aren't resource forks as errors."""
+
+    def test_short_file(self):
+        with tempfile.TemporaryDirectory() as tmp:
+            short = os.path.join(tmp, 'short.rsrc')
+            with open(short, 'wb') as f:
+                f.write(b'\x00\x00\x01\x00')
+            result = run_cli('--diff', short, os.path.join(FIXTURES_DIR, 'mac_single.rsrc'))
+        self.assertEqual(result.returncode, EXIT_ERROR)
+        self.assertIn('Could not compare', result.stdout)
+        self.assertNotIn('Traceback', result.stderr)
+
+class UniversalBinaryTest(unittest.TestCase):
+    """Slices that disagree on the effect version are reported, not silently picked from."""
+
+    def test_ambiguous_slices(self):
+        images = [(cpu_type, thin_macho_with_rsrc(cpu_type, PiplBuilder().add_name('Glow')
+                                                  .add_version(VersionInfo(1, 0, build, Stage.RELEASE, 0)).build_mac()))
+                  for cpu_type, build in ((0x01000007, 1), (0x0100000C, 2))]
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, 'Glow.dylib')
+            with open(path, 'wb') as f:
+                f.write(universal_macho(*images))
+            result = run_cli(path)
+            picked = run_cli('-q', '--arch', 'arm64', path)
+        self.assertEqual(result.returncode, EXIT_ERROR)
+        self.assertIn('use --arch to pick one', result.stdout)
+        self.assertEqual(picked.stdout.strip(), '1.0.2 Release (Build 0)')
+
+class MaxPropertiesTest(unittest.TestCase):
+    """--max-properties applies to every input format."""
+
+    def test_mach_o(self):
+        rsrc = PiplBuilder().add_name('Glow').add_version(VersionInfo(1, 0, 0, Stage.RELEASE, 0)).build_mac()
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, 'Glow.dylib')
+            with open(path, 'wb') as f:
+                f.write(thin_macho_with_rsrc(0x0100000C, rsrc))
+            limited = run_cli('--max-properties', '1', path)
+            unlimited = run_cli('-q', '--max-properties', '2', path)
+        self.assertEqual(limited.returncode, EXIT_ERROR)
+        self.assertIn('more than the maximum of 1', limited.stdout)
+        self.assertEqual(unlimited.stdout.strip(), '1.0.0 Release (Build 0)')
+
+class RequiredPropertiesTest(unittest.TestCase):
+    """Only After Effects effects are warned about missing effect properties."""
+
+    def check_warning(self, kind: bytes, expected: bool):
+        rsrc = (PiplBuilder().add_raw(b'kind', kind).add_name('Glow')
+                .add_version(VersionInfo(1, 0, 0, Stage.RELEASE, 0)).build_mac())
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, 'Glow.rsrc')
+            with open(path, 'wb') as f:
+                f.write(rsrc)
+            result = run_cli(path)
+        self.assertEqual('Missing properties required by After Effects' in result.stdout, expected)
+
+    def test_effect(self):
+        self.check_warning(b'eFKT', True)
+
+    def test_aegp(self):
+        self.check_warning(b'AEgx', False)
+
+class HfsImageTest(unittest.TestCase):
+    """HFS disk images are recognised but not read."""
+
+    def test_unsupported(self):
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, 'Plugins.img')
+            with open(path, 'wb') as f:
+                f.write(bytes(HFS_VOLUME_HEADER_OFFSET) + b'H+' + bytes(510))
+            result = run_cli(path)
+        self.assertEqual(result.returncode, EXIT_UNKNOWN_FORMAT)
+        self.assertIn('HFS images are not supported', result.stdout)
+
+def appledouble(rsrc: bytes) -> bytes:
+    """An AppleDouble file holding `rsrc` as its only (resource fork) entry."""
+    return struct.pack('>II16sHIII', 0x00051607, 0x00020000, bytes(16), 1, 2, 38, len(rsrc)) + rsrc
+
+class AppleDoubleTest(unittest.TestCase):
+    """Inspecting an AppleDouble file reads the resource fork inside it."""
+
+    def setUp(self):
+        self.tmp = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmp.cleanup)
+        self.fixture = os.path.join(FIXTURES_DIR, 'mac_single.rsrc')
+        self.path = os.path.join(self.tmp.name, '._Glow.rsrc')
+        with open(self.fixture, 'rb') as src, open(self.path, 'wb') as f:
+            f.write(appledouble(src.read()))
+
+    def test_dump_offsets(self):
+        wrapped = run_cli('dump', '--key', 'eVER', self.path)
+        plain = run_cli('dump', '--key', 'eVER', self.fixture)
+        self.assertEqual(wrapped.returncode, 0)
+        self.assertEqual(wrapped.stdout, plain.stdout)
+
+    def test_list(self):
+        result = run_cli('--list', self.path)
+        self.assertEqual(result.returncode, 0)
+        self.assertIn('1 resources, 124 bytes of resource data', result.stdout)
+
+class StatsTest(unittest.TestCase):
+    """--stats summarizes the resource maps of the scanned resource forks."""
+
+    def test_resource_type_totals(self):
+        paths = [os.path.join(FIXTURES_DIR, name) for name in ('mac_single.rsrc', 'mac_multi_type.rsrc')]
+        result = run_cli('--stats', '--format', 'json', *paths)
+        stats = json.loads(result.stdout)['stats']
+        self.assertEqual(stats['resources'], 4)
+        self.assertEqual(stats['resource_data_bytes'], 124 + 13 + 108 + 6)
+        self.assertEqual(stats['most_common_resource_type'], 'PiPL')
+
+if __name__ == '__main__':
+    unittest.main()
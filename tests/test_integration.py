@@ -0,0 +1,174 @@
+"""Integration tests that parse the binary fixtures in tests/fixtures."""
+
+import os
+import struct
+import tempfile
+import unittest
+
+from ae_pipl_extractor import detect_file_type, parse_file, parse_rsrc_file
+from pipl_builder import PiplBuilder, patch_8bim_version, write_8bim_pipl
+from pipl_parser import PiplReader, find_property, find_property_range, parse_pipl_data
+from pipl_types import FileTooSmallError, Stage, UnsupportedPiplVersionError, VersionInfo, decode_effect_version, decode_string
+from resource_fork_parser import (
+    RESOURCE_MAP_HEADER_SIZE, RESOURCE_MAP_TYPE_LIST_OFFSET_FIELD, PiplForkReader, ResourceForkParser, ResourceTypeSummary,
+    extract_pipl_bytes, list_resource_types
+)
+from tests.make_fixtures import FIXTURES_DIR, build_fixtures
+
+def fixture_path(name: str) -> str:
+    return os.path.join(FIXTURES_DIR, name)
+
+def properties_by_type(properties):
+    return {prop.property_type: prop for prop in properties}
+
+class FixtureIntegrationTest(unittest.TestCase):
+    """Parse each fixture end to end and check the decoded PiPL values."""
+
+    EXPECTED_VERSIONS = {
+        'mac_single.rsrc': VersionInfo(5, 14, 0, Stage.RELEASE, 3),
+        'mac_multi_type.rsrc': VersionInfo(1, 2, 3, Stage.BETA, 42),
+        'bim_stream.rsrc': VersionInfo(2, 0, 1, Stage.ALPHA, 7)
+    }
+
+    def test_fixtures_are_up_to_date(self):
+        for name, data in build_fixtures().items():
+            with self.subTest(fixture=name):
+                with open(fixture_path(name), 'rb') as f:
+                    self.assertEqual(f.read(), data, "run `python3 -m tests.make_fixtures`")
+
+    def test_effect_version(self):
+        for name, expected in self.EXPECTED_VERSIONS.items():
+            with self.subTest(fixture=name):
+                properties = properties_by_type(ResourceForkParser(fixture_path(name)).parse_pipl_properties())
+                self.assertEqual(decode_effect_version(properties['eVER'].data), expected)
+
+    def test_name_and_category(self):
+        properties = properties_by_type(ResourceForkParser(fixture_path('mac_single.rsrc')).parse_pipl_properties())
+        self.assertEqual(decode_string(properties['name'].data), 'Glow')
+        self.assertEqual(decode_string(properties['catg'].data), 'Stylize')
+        self.assertEqual(bytes(properties['kind'].data), b'eFKT')
+
+    def test_resource_map(self):
+        parser = ResourceForkParser(fixture_path('mac_multi_type.rsrc'))
+        entries = [(entry.resource_type, entry.resource_id) for entry in parser.get_resource_entries()]
+        self.assertEqual(entries, [('STR ', 128), ('PiPL', 16000), ('vers', 1)])
+
+    def test_list_resource_types(self):
+        with open(fixture_path('mac_multi_type.rsrc'), 'rb') as f:
+            summaries = list_resource_types(f.read())
+        self.assertEqual(summaries, [ResourceTypeSummary('STR ', 1, 13), ResourceTypeSummary('PiPL', 1, 108),
+                                     ResourceTypeSummary('vers', 1, 6)])
+
+    def test_extract_pipl_bytes(self):
+        with open(fixture_path('mac_multi_type.rsrc'), 'rb') as f:
+            blobs = extract_pipl_bytes(f.read())
+        self.assertEqual(len(blobs), 1)
+        properties = properties_by_type(parse_pipl_data(blobs[0]))
+        self.assertEqual(decode_effect_version(properties['eVER'].data), self.EXPECTED_VERSIONS['mac_multi_type.rsrc'])
+
+        start, end = find_property_range(blobs[0], b'eVER')
+        self.assertEqual(blobs[0][start:start + 8], b'8BIMeVER')
+        self.assertEqual(blobs[0][start + 16:end], bytes(properties['eVER'].data))
+
+    def test_type_list_follows_resource_map_header(self):
+        parser = ResourceForkParser(fixture_path('mac_multi_type.rsrc'))
+        map_offset = parser._read_big_endian_uint32(4)
+        self.assertEqual(parser._read_big_endian_uint16(map_offset + RESOURCE_MAP_TYPE_LIST_OFFSET_FIELD),
+                         RESOURCE_MAP_HEADER_SIZE)
+        # The type count (stored minus one) is the first field after the header
+        self.assertEqual(parser._read_big_endian_uint16(map_offset + RESOURCE_MAP_HEADER_SIZE), 2)
+
+    def test_streaming_reader_matches_parser(self):
+        path = fixture_path('mac_multi_type.rsrc')
+        parser = ResourceForkParser(path)
+        with open(path, 'rb') as f:
+            reader = PiplForkReader.open(f)
+            for entry in parser.get_resource_entries():
+                with self.subTest(resource=(entry.resource_type, entry.resource_id)):
+                    self.assertEqual(reader.resource_data(entry.resource_type.encode('mac_roman'), entry.resource_id),
+                                     bytes(entry.read_data(parser.data)))
+            self.assertIsNone(reader.resource_data(b'PiPL', 1))
+
+    def test_cli_parse(self):
+        path = fixture_path('mac_single.rsrc')
+        self.assertEqual(detect_file_type(path), 'rsrc')
+        properties = properties_by_type(parse_file(path, 'rsrc'))
+        self.assertEqual(decode_effect_version(properties['eVER'].data), self.EXPECTED_VERSIONS['mac_single.rsrc'])
+
+class ShortInputTest(unittest.TestCase):
+    """Inputs too short to hold a single property header."""
+
+    def test_seven_bytes_have_no_properties(self):
+        self.assertEqual(ResourceForkParser.from_bytes(b'8BIMeVE').parse_pipl_properties(), [])
+
+    def test_truncated_header_is_not_read(self):
+        # A signature and key whose length field runs past the end of the data
+        self.assertEqual(ResourceForkParser.from_bytes(b'8BIMeVER\x00\x00\x00\x00\x00\x00').parse_pipl_properties(), [])
+
+    def test_signature_near_end_is_not_read(self):
+        # A complete property followed by an '8BIM' less than 16 bytes from the end
+        data = b'8BIMname\x00\x00\x00\x00\x00\x00\x00\x04\x03Fx\x00' + b'8BIMeVER\x00\x00\x00\x00\x00'
+        properties = ResourceForkParser.from_bytes(data).parse_pipl_properties()
+        self.assertEqual([prop.property_type for prop in properties], ['name'])
+
+    def test_files_under_16_bytes_are_rejected(self):
+        for size in (0, 8, 15):
+            with self.subTest(size=size):
+                with self.assertRaises(FileTooSmallError) as cm:
+                    ResourceForkParser.from_bytes(b'8BIM'.ljust(size, b'\x00')[:size]).parse_pipl_entries()
+                self.assertEqual((cm.exception.size, cm.exception.minimum), (size, 16))
+
+    def test_parse_rsrc_file_raises_for_short_file(self):
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, 'short.rsrc')
+            with open(path, 'wb') as f:
+                f.write(b'8BIM')
+            with self.assertRaises(FileTooSmallError):
+                parse_rsrc_file(path)
+
+class PiplBuilderRoundTripTest(unittest.TestCase):
+    """Data written by PiplBuilder reads back through the parsers unchanged."""
+
+    def test_mac_round_trip(self):
+        version = VersionInfo(3, 1, 4, Stage.DEVELOP, 159)
+        data = PiplBuilder().add_name('Odd Length Name').add_version(version).build_mac()
+
+        properties = properties_by_type(ResourceForkParser.from_bytes(data).parse_pipl_properties())
+        self.assertEqual(decode_string(properties['name'].data), 'Odd Length Name')
+        self.assertEqual(decode_effect_version(properties['eVER'].data), version)
+
+    def test_reader_round_trip_both_layouts(self):
+        version = VersionInfo(12, 0, 2, Stage.RELEASE, 8)
+        builder = PiplBuilder().add_name('Glow').add_category('Stylize').add_version(version)
+
+        for layout, data in (('mac', builder.build_mac()), ('windows', builder.build_windows())):
+            with self.subTest(layout=layout):
+                properties = properties_by_type(parse_pipl_data(data))
+                self.assertEqual(list(properties), ['name', 'catg', 'eVER'])
+                self.assertEqual(decode_effect_version(properties['eVER'].data), version)
+                self.assertEqual(decode_string(find_property(data, b'catg').data), 'Stylize')
+
+    def test_pipl_version_is_checked(self):
+        data = PiplBuilder().add_name('Glow').build_mac()
+        self.assertEqual(PiplReader(data).pipl_format_version, 0)
+
+        unsupported = struct.pack('>I', 1) + data[4:]
+        with self.assertRaises(UnsupportedPiplVersionError) as context:
+            parse_pipl_data(unsupported)
+        self.assertEqual(context.exception.found, 1)
+
+    def test_8bim_writer_round_trip(self):
+        path = fixture_path('mac_single.rsrc')
+        properties = ResourceForkParser(path).parse_pipl_properties()
+        data = write_8bim_pipl(properties)
+
+        self.assertEqual(ResourceForkParser.from_bytes(data).parse_pipl_properties(), properties)
+
+        version = VersionInfo(5, 15, 0, Stage.BETA, 1)
+        patched = properties_by_type(ResourceForkParser.from_bytes(patch_8bim_version(data, version))
+                                     .parse_pipl_properties())
+        self.assertEqual(decode_effect_version(patched['eVER'].data), version)
+        self.assertEqual(decode_string(patched['name'].data), 'Glow')
+
+if __name__ == '__main__':
+    unittest.main()
@@ -0,0 +1,119 @@
+"""Unit tests for the PiPL value decoders in pipl_types."""
+
+import random
+import unittest
+import warnings
+
+from pipl_types import (
+    PF_VERS_BUGFIX_BITS, PF_VERS_BUGFIX_SHIFT, PF_VERS_BUILD_BITS, PF_VERS_BUILD_SHIFT, PF_VERS_STAGE_BITS,
+    PF_VERS_STAGE_SHIFT, PF_VERS_SUBVERS_BITS, PF_VERS_SUBVERS_SHIFT, PF_VERS_VERS_BITS, PF_VERS_VERS_HIGH_BITS,
+    PF_VERS_VERS_HIGH_SHIFT, PF_VERS_VERS_LOW_SHIFT, PF_VERS_VERS_SHIFT, PluginMetadata, Stage, VersionInfo,
+    encode_pf_version, extract_pf_version
+)
+
+class ExtractPfVersionTest(unittest.TestCase):
+    """Boundary conditions of the PF_VERSION bit layout."""
+
+    def test_zero(self):
+        self.assertEqual(extract_pf_version(0), VersionInfo.zero())
+
+    def test_release_stage(self):
+        self.assertEqual(extract_pf_version(3 << 9).stage, Stage.RELEASE)
+
+    def test_max_build(self):
+        self.assertEqual(extract_pf_version(0x1ff).build, 511)
+
+    def test_max_version_spans_split_fields(self):
+        # The major version is split into 3 low bits at 19-21 and 4 high bits at 26-29
+        encoded = (0xf << 26) | (0x7 << 19)
+        self.assertEqual(extract_pf_version(encoded), VersionInfo(127, 0, 0, Stage.DEVELOP, 0))
+
+    def test_known_plugin_value(self):
+        # eVER of the example plugin shown in the README
+        self.assertEqual(extract_pf_version(0x000b9801), VersionInfo(1, 7, 3, Stage.DEVELOP, 1))
+
+    def test_str_of_known_value(self):
+        # The major version 21 needs both of its split bit fields
+        self.assertEqual(str(extract_pf_version(0x0A288001)), '21.1.0 Develop (Build 1)')
+
+class PfVersionFieldTest(unittest.TestCase):
+    """Each bit field set to its maximum on its own is extracted without touching the others."""
+
+    def test_build(self):
+        encoded = PF_VERS_BUILD_BITS << PF_VERS_BUILD_SHIFT
+        self.assertEqual(extract_pf_version(encoded), VersionInfo(0, 0, 0, Stage.DEVELOP, 511))
+
+    def test_stage(self):
+        encoded = PF_VERS_STAGE_BITS << PF_VERS_STAGE_SHIFT
+        self.assertEqual(extract_pf_version(encoded), VersionInfo(0, 0, 0, Stage.RELEASE, 0))
+
+    def test_bugfix(self):
+        encoded = PF_VERS_BUGFIX_BITS << PF_VERS_BUGFIX_SHIFT
+        self.assertEqual(extract_pf_version(encoded), VersionInfo(0, 0, 15, Stage.DEVELOP, 0))
+
+    def test_subversion(self):
+        encoded = PF_VERS_SUBVERS_BITS << PF_VERS_SUBVERS_SHIFT
+        self.assertEqual(extract_pf_version(encoded), VersionInfo(0, 15, 0, Stage.DEVELOP, 0))
+
+    def test_version_low_bits(self):
+        encoded = PF_VERS_VERS_BITS << PF_VERS_VERS_SHIFT
+        self.assertEqual(extract_pf_version(encoded), VersionInfo(7, 0, 0, Stage.DEVELOP, 0))
+
+    def test_version_high_bits(self):
+        encoded = PF_VERS_VERS_HIGH_BITS << PF_VERS_VERS_HIGH_SHIFT
+        self.assertEqual(extract_pf_version(encoded), VersionInfo(15 << PF_VERS_VERS_LOW_SHIFT, 0, 0, Stage.DEVELOP, 0))
+
+    def test_version_low_shift(self):
+        # The lowest high bit lands just above the three low bits
+        encoded = 1 << PF_VERS_VERS_HIGH_SHIFT
+        self.assertEqual(extract_pf_version(encoded).version, 1 << PF_VERS_VERS_LOW_SHIFT)
+
+    def test_gap_bits_ignored(self):
+        # Bits 22-25 sit between the low and high version fields
+        self.assertEqual(extract_pf_version(0xf << 22), VersionInfo.zero())
+
+    def test_top_bits_ignored(self):
+        self.assertEqual(extract_pf_version(0x3 << 30), VersionInfo.zero())
+
+class EncodePfVersionTest(unittest.TestCase):
+    """Encoding is the inverse of extract_pf_version for every in-range value."""
+
+    # Bits 22-25 and 30-31 are not part of the PF_VERSION layout
+    USED_BITS = 0x3C3FFFFF
+
+    def test_round_trip(self):
+        rng = random.Random(0)
+        for _ in range(1000):
+            encoded = rng.getrandbits(32) & self.USED_BITS
+            info = extract_pf_version(encoded)
+            with self.subTest(encoded=hex(encoded)):
+                self.assertEqual(info.to_encoded(), encoded)
+                self.assertEqual(int(info), encoded)
+
+    def test_out_of_range(self):
+        with self.assertRaises(ValueError):
+            VersionInfo(128, 0, 0, Stage.RELEASE, 0).to_encoded()
+        with self.assertRaises(ValueError):
+            VersionInfo(1, 0, 0, Stage.RELEASE, 512).to_encoded()
+
+    def test_deprecated_function(self):
+        info = VersionInfo(1, 7, 3, Stage.DEVELOP, 1)
+        with warnings.catch_warnings(record=True) as caught:
+            warnings.simplefilter('always')
+            self.assertEqual(encode_pf_version(info), info.to_encoded())
+        self.assertTrue(any(issubclass(w.category, DeprecationWarning) for w in caught))
+
+class PluginMetadataDisplayNameTest(unittest.TestCase):
+    """display_name falls back from the name to the match name to a placeholder."""
+
+    def test_name(self):
+        self.assertEqual(PluginMetadata(name='Glow', match_name='ADBE Glow').display_name(), 'Glow')
+
+    def test_match_name_fallback(self):
+        self.assertEqual(PluginMetadata(match_name='ADBE Glow').display_name(), 'ADBE Glow')
+
+    def test_unknown(self):
+        self.assertEqual(PluginMetadata().display_name(), '<unknown>')
+
+if __name__ == '__main__':
+    unittest.main()